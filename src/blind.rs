@@ -1,15 +1,28 @@
 ﻿use crate::{
     blind::{BlindType::*, BossBlindType::*},
+    card::Card,
+    chance::{Roller, Seeded, odds},
+    controller::ActionError,
     event_list::HandPlayedEventData,
     hands::{Hand, HandType},
+    joker::{Joker, JokerType::Pareidolia},
     misc::Also,
     run::RunData,
+    scoring::{score_distribution_capped, score_sampled_mean},
     seeding::random_element,
+    stake::Stake,
+    vouchers::Voucher::{DirectorsCut, Retcon},
 };
 use itertools::Itertools;
-use strum::{Display, EnumCount, EnumIter};
+use std::collections::HashMap;
+use strum::{Display, EnumCount, EnumIter, EnumString};
 
-#[derive(Debug, Default, PartialEq)]
+/// Outcome combinations [`Blind::best_plays`] enumerates per subset. Every subset is up to that
+/// many scoring passes, so this is far below [`MAX_OUTCOMES`](crate::scoring::MAX_OUTCOMES).
+pub const PLAY_OUTCOMES: usize = 256;
+
+#[derive(Debug, Default, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Blind {
     pub chips: f64,
     pub mult: f64,
@@ -18,13 +31,17 @@ pub struct Blind {
     pub cards: Vec<usize>,
     pub selected: Hand,
     pub held: Vec<usize>,
+    /// The held cards the boss dealt face down. Indices into `RunData::cards`.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub face_down: Vec<usize>,
     pub score: f64,
     pub requirement: f64,
     pub hands: u32,
     pub discards: u32,
 }
 
-#[derive(Debug, Default, PartialEq)]
+#[derive(Debug, Default, PartialEq, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum BlindType {
     #[default]
     Small,
@@ -37,9 +54,21 @@ impl BlindType {
         match self {
             Boss(TheEye) => Some(BossBlindData::TheEye { was_already_played: [false; _] }),
             Boss(TheMouth) => Some(BossBlindData::TheMouth { allowed_hand: None }),
+            Boss(TheHouse) => Some(BossBlindData::TheHouse { dealt: false }),
+            Boss(TheFish) => Some(BossBlindData::TheFish { prepped: false }),
             _ => None,
         }
     }
+
+    pub fn reward(&self, stake: Stake) -> f64 {
+        match self {
+            Small if stake >= Stake::Red => 0.,
+            Small => 3.,
+            Big => 4.,
+            Boss(boss) if SHOWDOWN_BOSSES.contains(boss) => 8.,
+            Boss(_) => 5.,
+        }
+    }
 }
 
 impl RunData {
@@ -63,10 +92,32 @@ impl RunData {
         **random_element(&allowed_bosses, self.rng.seed("boss"))
             .also(|boss| self.times_boss_used[***boss as usize] += 1)
     }
+
+    /// Director's Cut allows one reroll per ante, Retcon unlimited ones. Each costs $10.
+    pub fn reroll_boss(&mut self) -> Option<BossBlindType> {
+        let allowed = self.vouchers[Retcon as usize]
+            || (self.vouchers[DirectorsCut as usize] && !self.boss_rerolled);
+
+        if !allowed || self.money < 10. {
+            return None;
+        }
+
+        self.money -= 10.;
+        self.boss_rerolled = true;
+        self.this_antes_boss = self.current_boss();
+
+        if let Boss(_) = self.blind_on_deck {
+            self.blind_on_deck = Boss(self.this_antes_boss);
+        }
+
+        Some(self.this_antes_boss)
+    }
 }
 
 #[repr(u8)]
-#[derive(Debug, PartialEq, Copy, Clone, Display, EnumCount, EnumIter)]
+#[derive(Debug, PartialEq, Copy, Clone, Display, EnumCount, EnumIter, EnumString)]
+#[strum(ascii_case_insensitive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum BossBlindType {
     TheHook,
     TheOx,
@@ -161,24 +212,138 @@ impl BossBlindType {
     }
 }
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum BossBlindData {
-    TheEye { was_already_played: [bool; HandType::COUNT] },
-    TheMouth { allowed_hand: Option<HandType> },
+    TheEye {
+        was_already_played: [bool; HandType::COUNT],
+    },
+    TheMouth {
+        allowed_hand: Option<HandType>,
+    },
+    /// Whether the first hand was dealt, face down.
+    TheHouse {
+        dealt: bool,
+    },
+    /// Whether a hand was played since the last draw, which the next draw deals face down.
+    TheFish {
+        prepped: bool,
+    },
+}
+
+/// A candidate hand from [`Blind::best_plays`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Play {
+    /// Indices into `held`, in the order to select them.
+    pub held: Vec<usize>,
+    pub hand_type: HandType,
+    pub score: f64,
 }
 
 impl Blind {
+    /// Whether the boss would let `hand_type` score, without marking it as played.
+    pub fn allows(&self, hand_type: HandType) -> bool {
+        match &self.blind_data {
+            Some(BossBlindData::TheEye { was_already_played }) => {
+                !was_already_played[hand_type as usize]
+            }
+            Some(BossBlindData::TheMouth { allowed_hand }) => {
+                allowed_hand.is_none_or(|allowed| allowed == hand_type)
+            }
+            Some(BossBlindData::TheHouse { .. } | BossBlindData::TheFish { .. }) | None => true,
+        }
+    }
+
+    /// Every 1 to 5 card subset of `held`, best expected score first. Ties prefer playing fewer
+    /// cards.
+    ///
+    /// Hands with more than [`PLAY_OUTCOMES`] random outcomes use the mean of as many sampled
+    /// plays instead, see [`score_sampled_mean`].
+    /// Hands the boss won't score are ranked last with a score of 0. Subsets with the same cards
+    /// are only scored once.
+    pub fn best_plays(&self, jokers: &[Joker], data: &RunData) -> Vec<Play> {
+        if self.hands == 0 {
+            return Vec::new();
+        }
+
+        let mut scores: HashMap<Vec<&Card>, f64> = HashMap::new();
+        let mut plays = Vec::new();
+
+        for len in 1..=self.held.len().min(5) {
+            for held in (0..self.held.len()).combinations(len) {
+                let mut hand = Hand { cards: [0; 5], len };
+                for (slot, &idx) in held.iter().enumerate() {
+                    hand.cards[slot] = self.held[idx];
+                }
+
+                let resolved = hand.resolve(&data.cards);
+                let hand_type = resolved.hand_type();
+                let score = if self.allows(hand_type) {
+                    let key = resolved.0.into_iter().sorted().collect_vec();
+                    *scores.entry(key).or_insert_with(|| {
+                        score_distribution_capped(jokers, data, self, &hand, PLAY_OUTCOMES)
+                            .map(|distribution| distribution.mean())
+                            .or_else(|| {
+                                score_sampled_mean(jokers, data, self, &hand, PLAY_OUTCOMES)
+                            })
+                            .unwrap_or(0.)
+                    })
+                } else {
+                    0.
+                };
+
+                plays.push(Play { held, hand_type, score });
+            }
+        }
+
+        plays.sort_by(|a, b| b.score.total_cmp(&a.score).then(a.held.len().cmp(&b.held.len())));
+        plays
+    }
+
+    /// Indices into `held` worth discarding, most expendable first.
+    ///
+    /// Cards in the best play are kept. The rest are ranked by the best play they are part of, then
+    /// by how many held cards share their suit or rank.
+    pub fn discard_candidates(&self, data: &RunData, plays: &[Play]) -> Vec<usize> {
+        let Some(best) = plays.first() else {
+            return Vec::new();
+        };
+
+        let usefulness = |idx: usize| {
+            plays.iter().find(|play| play.held.contains(&idx)).map_or(0., |play| play.score)
+        };
+        let related = |idx: usize| {
+            let card = &data.cards[self.held[idx]];
+            self.held
+                .iter()
+                .filter(|&&other| {
+                    let other = &data.cards[other];
+                    other.suit == card.suit || other.rank == card.rank
+                })
+                .count()
+        };
+
+        (0..self.held.len())
+            .filter(|idx| !best.held.contains(idx))
+            .sorted_by(|&a, &b| {
+                usefulness(a).total_cmp(&usefulness(b)).then(related(a).cmp(&related(b)))
+            })
+            .take(5)
+            .collect()
+    }
+
     pub fn hand_played(&mut self, data: &mut RunData, event: &mut HandPlayedEventData) {
         let hand_type = event.hand.resolve(&data.cards).hand_type();
 
-        match &mut self.blind_type {
-            Boss(TheArm) => data.change_hand_level(hand_type, -1),
+        // Blinds built by hand may be missing their boss data
+        if self.blind_data.is_none() {
+            self.blind_data = self.blind_type.default_data();
+        }
 
-            Boss(TheEye) => {
-                let Some(BossBlindData::TheEye { was_already_played }) = &mut self.blind_data
-                else {
-                    unreachable!()
-                };
+        match (self.blind_type, &mut self.blind_data) {
+            (Boss(TheArm), _) => self.change_hand_level(data, hand_type, -1),
+            (Boss(TheFish), Some(BossBlindData::TheFish { prepped })) => *prepped = true,
 
+            (Boss(TheEye), Some(BossBlindData::TheEye { was_already_played })) => {
                 if was_already_played[hand_type as usize] {
                     event.allowed = false;
                     return;
@@ -187,47 +352,116 @@ impl Blind {
                 was_already_played[hand_type as usize] = true;
             }
 
-            Boss(TheMouth) => {
-                let Some(BossBlindData::TheMouth { allowed_hand }) = &mut self.blind_data else {
-                    unreachable!()
-                };
-
-                if hand_type != *allowed_hand.get_or_insert(hand_type) {
-                    event.allowed = false;
-                }
+            (Boss(TheMouth), Some(BossBlindData::TheMouth { allowed_hand })) => {
+                event.allowed &= hand_type == *allowed_hand.get_or_insert(hand_type);
             }
 
             _ => {}
         }
     }
 
-    pub fn draw(&mut self, data: &RunData) {
+    /// Levels the hand being played, carrying the change over to its chips and mult.
+    pub fn change_hand_level(&mut self, data: &mut RunData, hand_type: HandType, amount: i32) {
+        let idx = hand_type as usize;
+        let (chips, mult) = (data.base_chips[idx], data.base_mult[idx]);
+        data.change_hand_level(hand_type, amount);
+
+        self.chips += data.base_chips[idx] as f64 - chips as f64;
+        self.mult += data.base_mult[idx] as f64 - mult as f64;
+    }
+
+    pub fn draw(&mut self, jokers: &[Joker], data: &mut RunData) {
+        // Blinds built by hand may be missing their boss data
+        if self.blind_data.is_none() {
+            self.blind_data = self.blind_type.default_data();
+        }
+
         for _ in self.held.len()..data.hand_size as usize {
             let Some(card) = self.cards.pop() else {
-                return;
+                break;
             };
 
             self.held.push(card);
+            if self.stays_flipped(jokers, data, card) {
+                self.face_down.push(card);
+            }
+        }
+
+        match &mut self.blind_data {
+            Some(BossBlindData::TheHouse { dealt }) => *dealt = true,
+            Some(BossBlindData::TheFish { prepped }) => *prepped = false,
+            _ => {}
         }
     }
 
-    pub fn discard(&mut self) -> Option<()> {
+    /// Whether the boss deals `card` face down, the game's `Blind:stay_flipped`.
+    fn stays_flipped(&self, jokers: &[Joker], data: &mut RunData, card: usize) -> bool {
+        match (self.blind_type, &self.blind_data) {
+            (Boss(TheWheel), _) => Seeded.chance(&mut data.rng, "wheel", odds(jokers, 7.)),
+            (Boss(TheHouse), Some(BossBlindData::TheHouse { dealt })) => !dealt,
+            (Boss(TheMark), _) => {
+                let card = &data.cards[card];
+                card.has_rank() && card.rank.is_face_card()
+                    || jokers.iter().any(|joker| joker.joker_type == Pareidolia && !joker.debuffed)
+            }
+            (Boss(TheFish), Some(BossBlindData::TheFish { prepped })) => *prepped,
+            _ => false,
+        }
+    }
+
+    /// The next `count` cards [`Blind::draw`] will take, in order. Indices into `RunData::cards`.
+    ///
+    /// This is the seeded order a player can't see, fair bots should use [`Outs`] instead.
+    ///
+    /// [`Outs`]: crate::draw::Outs
+    pub fn peek(&self, count: usize) -> Vec<usize> {
+        self.cards.iter().rev().take(count).copied().collect()
+    }
+
+    /// Discards the selected cards. Effects that trigger on discards, from jokers, seals or the
+    /// boss, aren't applied.
+    pub fn discard(&mut self) -> Result<(), ActionError> {
         if self.discards == 0 {
-            return None;
+            return Err(ActionError::NoDiscards);
+        }
+
+        if self.selected.len == 0 {
+            return Err(ActionError::NothingSelected);
         }
 
         self.remove_selected_from_hand();
         self.selected = Hand::default();
         self.discards -= 1;
 
-        Some(())
+        Ok(())
     }
 
-    pub fn prepare_play(&mut self, data: &RunData) -> Option<Hand> {
+    /// Checks that the selected cards can be played, including whether the boss allows them.
+    pub fn can_play(&self, data: &RunData) -> Result<(), ActionError> {
         if self.hands == 0 {
-            return None;
+            return Err(ActionError::NoHands);
+        }
+
+        if self.selected.len == 0 {
+            return Err(ActionError::NothingSelected);
         }
 
+        let hand_type = self.selected.resolve(&data.cards).hand_type();
+        if !self.allows(hand_type) {
+            return Err(ActionError::HandNotAllowed(hand_type));
+        }
+
+        Ok(())
+    }
+
+    /// Takes the selected cards out of `held` to be played, after checking [`Blind::can_play`].
+    pub fn prepare_play(&mut self, data: &RunData) -> Result<Hand, ActionError> {
+        self.can_play(data)?;
+        Ok(self.take_selected(data))
+    }
+
+    /// [`Blind::prepare_play`] without the checks, for hands that are played but won't score.
+    pub(crate) fn take_selected(&mut self, data: &RunData) -> Hand {
         let hand_type = self.selected.resolve(&data.cards).hand_type();
         self.chips = data.base_chips[hand_type as usize] as f64;
         self.mult = data.base_mult[hand_type as usize] as f64;
@@ -237,16 +471,39 @@ impl Blind {
         self.hands -= 1;
         self.selected = Hand::default();
 
-        Some(old_selected)
+        old_selected
     }
 
-    pub fn select(&mut self, idx: usize) {
-        if self.selected.len == 5 || self.selected.card_slice().contains(&idx) {
-            return;
+    /// Removes `destroyed` from `RunData::cards`, shifting the indices the blind holds.
+    pub(crate) fn destroy_cards(&mut self, data: &mut RunData, destroyed: &[usize]) {
+        for &idx in destroyed.iter().sorted().rev() {
+            data.cards.remove(idx);
+
+            for cards in [&mut self.cards, &mut self.held, &mut self.face_down] {
+                cards.retain(|&card| card != idx);
+                cards.iter_mut().filter(|card| **card > idx).for_each(|card| *card -= 1);
+            }
         }
+    }
 
-        self.selected.cards[self.selected.len] = self.held[idx];
+    /// Selects the card at `idx` in `held`.
+    pub fn select(&mut self, idx: usize) -> Result<(), ActionError> {
+        let Some(&card) = self.held.get(idx) else {
+            return Err(ActionError::OutOfRange(idx));
+        };
+
+        if self.selected.card_slice().contains(&card) {
+            return Err(ActionError::Deselection(idx));
+        }
+
+        if self.selected.len == 5 {
+            return Err(ActionError::SelectionFull);
+        }
+
+        self.selected.cards[self.selected.len] = card;
         self.selected.len += 1;
+
+        Ok(())
     }
 
     fn remove_selected_from_hand(&mut self) {
@@ -262,5 +519,7 @@ impl Blind {
                 self.held.remove(idx);
             }
         }
+
+        self.face_down.retain(|card| self.held.contains(card));
     }
 }
@@ -5,22 +5,37 @@
         joker::{JokerCreator, JokerRarityMode},
     },
     card::Card,
-    consumable::{PLANET_ORDER, Planet, Spectral, Spectral::BlackHole, Tarot},
+    consumable::{
+        Consumable::{SpectralCard, TarotCard},
+        PLANET_ORDER, Planet, Spectral,
+        Spectral::BlackHole,
+        Tarot,
+    },
+    controller::ActionError,
     hands::HandType,
-    joker::Joker,
+    joker::{Joker, JokerEdition::Negative},
     run::{Run, RunData},
     seeding::math,
     vouchers::Voucher,
 };
+use BoosterPackType::*;
 use Spectral::TheSoul;
 use derive_more::From;
 use itertools::Itertools;
 use num_derive::FromPrimitive;
-use std::array;
-use strum::{Display, EnumCount, EnumIter, IntoEnumIterator};
+use std::{
+    error::Error,
+    fmt::{Display, Formatter},
+    iter::repeat_with,
+};
+use strum::{Display, EnumCount, EnumIter, EnumString, IntoEnumIterator};
 
 #[repr(u8)]
-#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumCount, EnumIter, FromPrimitive, Display)]
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, EnumCount, EnumIter, FromPrimitive, Display, EnumString,
+)]
+#[strum(ascii_case_insensitive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum BoosterPackType {
     ArcanaNormal,
     ArcanaJumbo,
@@ -39,23 +54,130 @@ pub enum BoosterPackType {
     SpectralMega,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumCount, EnumIter, Display)]
+pub enum BoosterPackKind {
+    Arcana,
+    Celestial,
+    Standard,
+    Buffoon,
+    Spectral,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumCount, EnumIter, Display)]
+pub enum BoosterPackSize {
+    Normal,
+    Jumbo,
+    Mega,
+}
+
+impl BoosterPackType {
+    #[must_use]
+    pub const fn kind(&self) -> BoosterPackKind {
+        match self {
+            ArcanaNormal | ArcanaJumbo | ArcanaMega => BoosterPackKind::Arcana,
+            CelestialNormal | CelestialJumbo | CelestialMega => BoosterPackKind::Celestial,
+            StandardNormal | StandardJumbo | StandardMega => BoosterPackKind::Standard,
+            BuffoonNormal | BuffoonJumbo | BuffoonMega => BoosterPackKind::Buffoon,
+            SpectralNormal | SpectralJumbo | SpectralMega => BoosterPackKind::Spectral,
+        }
+    }
+
+    #[must_use]
+    pub const fn size(&self) -> BoosterPackSize {
+        match self {
+            ArcanaNormal | CelestialNormal | StandardNormal | BuffoonNormal | SpectralNormal => {
+                BoosterPackSize::Normal
+            }
+            ArcanaJumbo | CelestialJumbo | StandardJumbo | BuffoonJumbo | SpectralJumbo => {
+                BoosterPackSize::Jumbo
+            }
+            ArcanaMega | CelestialMega | StandardMega | BuffoonMega | SpectralMega => {
+                BoosterPackSize::Mega
+            }
+        }
+    }
+
+    /// Number of cards shown when the pack is opened.
+    #[must_use]
+    pub const fn card_count(&self) -> usize {
+        let small = matches!(self.kind(), BoosterPackKind::Buffoon | BoosterPackKind::Spectral);
+
+        match (self.size(), small) {
+            (BoosterPackSize::Normal, true) => 2,
+            (BoosterPackSize::Normal, false) => 3,
+            (_, true) => 4,
+            (_, false) => 5,
+        }
+    }
+
+    /// Number of cards the player may take from the pack.
+    #[must_use]
+    pub const fn picks(&self) -> usize {
+        match self.size() {
+            BoosterPackSize::Normal | BoosterPackSize::Jumbo => 1,
+            BoosterPackSize::Mega => 2,
+        }
+    }
+
+    /// The first of the game's art variants, which all behave the same.
+    #[must_use]
+    pub const fn game_name(&self) -> &'static str {
+        match self {
+            ArcanaNormal => "p_arcana_normal_1",
+            ArcanaJumbo => "p_arcana_jumbo_1",
+            ArcanaMega => "p_arcana_mega_1",
+            CelestialNormal => "p_celestial_normal_1",
+            CelestialJumbo => "p_celestial_jumbo_1",
+            CelestialMega => "p_celestial_mega_1",
+            StandardNormal => "p_standard_normal_1",
+            StandardJumbo => "p_standard_jumbo_1",
+            StandardMega => "p_standard_mega_1",
+            BuffoonNormal => "p_buffoon_normal_1",
+            BuffoonJumbo => "p_buffoon_jumbo_1",
+            BuffoonMega => "p_buffoon_mega_1",
+            SpectralNormal => "p_spectral_normal_1",
+            SpectralJumbo => "p_spectral_jumbo_1",
+            SpectralMega => "p_spectral_mega_1",
+        }
+    }
+
+    #[must_use]
+    pub const fn base_cost(&self) -> f64 {
+        match self.size() {
+            BoosterPackSize::Normal => 4.,
+            BoosterPackSize::Jumbo => 6.,
+            BoosterPackSize::Mega => 8.,
+        }
+    }
+}
+
+/// The cards shown by an opened pack.
 #[derive(Debug, Clone, PartialEq, EnumCount)]
 pub enum BoosterPackData {
-    ArcanaNormal([ArcanaCard; 3]),
-    ArcanaJumbo([ArcanaCard; 5]),
-    ArcanaMega([ArcanaCard; 5]),
-    CelestialNormal([CelestialCard; 3]),
-    CelestialJumbo([CelestialCard; 5]),
-    CelestialMega([CelestialCard; 5]),
-    StandardNormal([Card; 3]),
-    StandardJumbo([Card; 5]),
-    StandardMega([Card; 5]),
-    BuffoonNormal([Joker; 2]),
-    BuffoonJumbo([Joker; 4]),
-    BuffoonMega([Joker; 4]),
-    SpectralNormal([Spectral; 2]),
-    SpectralJumbo([Spectral; 4]),
-    SpectralMega([Spectral; 4]),
+    Arcana(Vec<ArcanaCard>),
+    Celestial(Vec<CelestialCard>),
+    Standard(Vec<Card>),
+    Buffoon(Vec<Joker>),
+    Spectral(Vec<Spectral>),
+}
+
+/// A card taken out of a pack.
+#[derive(Debug, Clone, PartialEq)]
+pub enum BoosterPackItem {
+    Arcana(ArcanaCard),
+    Celestial(CelestialCard),
+    Standard(Card),
+    Buffoon(Joker),
+    Spectral(Spectral),
+}
+
+/// An opened pack, see [`Run::open_booster_pack`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct BoosterPack {
+    pub pack_type: BoosterPackType,
+    pub data: BoosterPackData,
+    /// Starts at [`BoosterPackType::picks`].
+    pub picks_left: usize,
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, EnumCount, From)]
@@ -70,6 +192,75 @@ pub enum CelestialCard {
     BlackHole,
 }
 
+impl BoosterPackData {
+    #[must_use]
+    pub const fn kind(&self) -> BoosterPackKind {
+        match self {
+            Self::Arcana(_) => BoosterPackKind::Arcana,
+            Self::Celestial(_) => BoosterPackKind::Celestial,
+            Self::Standard(_) => BoosterPackKind::Standard,
+            Self::Buffoon(_) => BoosterPackKind::Buffoon,
+            Self::Spectral(_) => BoosterPackKind::Spectral,
+        }
+    }
+
+    #[must_use]
+    pub fn card_count(&self) -> usize {
+        match self {
+            Self::Arcana(cards) => cards.len(),
+            Self::Celestial(cards) => cards.len(),
+            Self::Standard(cards) => cards.len(),
+            Self::Buffoon(jokers) => jokers.len(),
+            Self::Spectral(cards) => cards.len(),
+        }
+    }
+
+    fn remove(&mut self, idx: usize) -> BoosterPackItem {
+        match self {
+            Self::Arcana(cards) => BoosterPackItem::Arcana(cards.remove(idx)),
+            Self::Celestial(cards) => BoosterPackItem::Celestial(cards.remove(idx)),
+            Self::Standard(cards) => BoosterPackItem::Standard(cards.remove(idx)),
+            Self::Buffoon(jokers) => BoosterPackItem::Buffoon(jokers.remove(idx)),
+            Self::Spectral(cards) => BoosterPackItem::Spectral(cards.remove(idx)),
+        }
+    }
+}
+
+/// Why [`BoosterPack::pick`] failed. The pack is left as it was.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PickError {
+    NoPicksLeft,
+    /// There's no card at this index.
+    OutOfRange(usize),
+}
+
+impl Display for PickError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PickError::NoPicksLeft => write!(f, "no picks left in this pack"),
+            PickError::OutOfRange(idx) => write!(f, "nothing at index {idx}"),
+        }
+    }
+}
+
+impl Error for PickError {}
+
+impl BoosterPack {
+    /// Takes the card at `idx` out of the pack, using up one pick.
+    pub fn pick(&mut self, idx: usize) -> Result<BoosterPackItem, PickError> {
+        if self.picks_left == 0 {
+            return Err(PickError::NoPicksLeft);
+        }
+
+        if idx >= self.data.card_count() {
+            return Err(PickError::OutOfRange(idx));
+        }
+
+        self.picks_left -= 1;
+        Ok(self.data.remove(idx))
+    }
+}
+
 impl From<Spectral> for CelestialCard {
     fn from(spectral: Spectral) -> Self {
         assert_eq!(spectral, BlackHole);
@@ -102,53 +293,88 @@ impl Run {
         unreachable!()
     }
 
-    pub fn open_booster_pack(&mut self, booster_pack_type: BoosterPackType) -> BoosterPackData {
-        let Self { data, .. } = self;
+    pub fn open_booster_pack(&mut self, pack_type: BoosterPackType) -> BoosterPack {
+        let count = pack_type.card_count();
+
+        let data = match pack_type.kind() {
+            BoosterPackKind::Arcana => BoosterPackData::Arcana(self.data.arcana(count)),
+            BoosterPackKind::Celestial => BoosterPackData::Celestial(self.data.celestial(count)),
+            BoosterPackKind::Standard => BoosterPackData::Standard(self.data.normal(count)),
+            BoosterPackKind::Buffoon => BoosterPackData::Buffoon(self.jokers(count)),
+            BoosterPackKind::Spectral => BoosterPackData::Spectral(self.data.spectral(count)),
+        };
 
-        match booster_pack_type {
-            BoosterPackType::ArcanaNormal => BoosterPackData::ArcanaNormal(data.arcana()),
-            BoosterPackType::ArcanaJumbo => BoosterPackData::ArcanaJumbo(data.arcana()),
-            BoosterPackType::ArcanaMega => BoosterPackData::ArcanaMega(data.arcana()),
-            BoosterPackType::CelestialNormal => BoosterPackData::CelestialNormal(data.celestial()),
-            BoosterPackType::CelestialJumbo => BoosterPackData::CelestialJumbo(data.celestial()),
-            BoosterPackType::CelestialMega => BoosterPackData::CelestialMega(data.celestial()),
-            BoosterPackType::SpectralNormal => BoosterPackData::SpectralNormal(data.spectral()),
-            BoosterPackType::SpectralJumbo => BoosterPackData::SpectralJumbo(data.spectral()),
-            BoosterPackType::SpectralMega => BoosterPackData::SpectralMega(data.spectral()),
-            BoosterPackType::BuffoonNormal => BoosterPackData::BuffoonNormal(self.jokers()),
-            BoosterPackType::BuffoonJumbo => BoosterPackData::BuffoonJumbo(self.jokers()),
-            BoosterPackType::BuffoonMega => BoosterPackData::BuffoonMega(self.jokers()),
-            BoosterPackType::StandardNormal => BoosterPackData::StandardNormal(data.normal()),
-            BoosterPackType::StandardJumbo => BoosterPackData::StandardJumbo(data.normal()),
-            BoosterPackType::StandardMega => BoosterPackData::StandardMega(data.normal()),
-        }
-    }
-
-    fn jokers<const N: usize>(&mut self) -> [Joker; N] {
-        let mut jokers = [const { Option::<Joker>::None }; N];
-
-        for idx in 0..N {
+        BoosterPack { pack_type, data, picks_left: pack_type.picks() }
+    }
+
+    /// Takes the card at `idx` out of `pack` and adds it to the run.
+    ///
+    /// Using Tarot and Spectral Cards isn't supported yet, so they're kept as consumables.
+    pub fn pick_from_booster_pack(
+        &mut self,
+        pack: &mut BoosterPack,
+        idx: usize,
+    ) -> Result<BoosterPackItem, ActionError> {
+        let has_room = match &pack.data {
+            BoosterPackData::Buffoon(jokers) => jokers
+                .get(idx)
+                .is_none_or(|joker| joker.edition == Negative || self.free_joker_slots() > 0),
+            BoosterPackData::Arcana(_) | BoosterPackData::Spectral(_) => {
+                self.data.consumables.len() < self.data.consumable_slots
+            }
+            BoosterPackData::Celestial(_) | BoosterPackData::Standard(_) => true,
+        };
+
+        if !has_room {
+            return Err(ActionError::NoRoom);
+        }
+
+        let item = pack.pick(idx)?;
+        match item.clone() {
+            BoosterPackItem::Arcana(ArcanaCard::Tarot(tarot)) => {
+                self.data.consumables.push(TarotCard(tarot))
+            }
+            BoosterPackItem::Arcana(ArcanaCard::Spectral(spectral))
+            | BoosterPackItem::Spectral(spectral) => {
+                self.data.consumables.push(SpectralCard(spectral))
+            }
+            BoosterPackItem::Celestial(CelestialCard::Planet(hand_type)) => {
+                self.data.change_hand_level(hand_type, 1)
+            }
+            BoosterPackItem::Celestial(CelestialCard::BlackHole) => {
+                HandType::iter().for_each(|hand_type| self.data.change_hand_level(hand_type, 1))
+            }
+            BoosterPackItem::Standard(card) => self.data.cards.push(card),
+            BoosterPackItem::Buffoon(joker) => self.jokers.push(joker),
+        }
+
+        Ok(item)
+    }
+
+    fn jokers(&mut self, count: usize) -> Vec<Joker> {
+        let mut jokers: Vec<Joker> = Vec::with_capacity(count);
+
+        for _ in 0..count {
             let joker = JokerCreator::builder()
                 .origin_key("buf")
                 .joker_rarity(JokerRarityMode::RandomNonLegendary)
-                .filter(&|joker_type| {
-                    !jokers[..idx].iter().any(|j| j.as_ref().unwrap().joker_type == *joker_type)
-                })
+                .filter(&|joker_type| !jokers.iter().any(|j| j.joker_type == *joker_type))
                 .dont_filter_on_showman()
+                .random_edition()
                 .build()
                 .create(self);
 
-            jokers[idx] = Some(joker);
+            jokers.push(joker);
         }
 
-        jokers.map(Option::unwrap)
+        jokers
     }
 }
 impl RunData {
-    fn arcana<const N: usize>(&mut self) -> [ArcanaCard; N] {
-        let mut arcana_cards = [None; N];
+    fn arcana(&mut self, count: usize) -> Vec<ArcanaCard> {
+        let mut arcana_cards = Vec::with_capacity(count);
 
-        for idx in 0..N {
+        for _ in 0..count {
             let card = if self.vouchers[Voucher::OmenGlobe as usize] && {
                 math::randomseed(self.rng.seed("omen_globe"));
                 math::random() > 0.8
@@ -157,9 +383,7 @@ impl RunData {
                     .type_key("Spectral")
                     .origin_key("ar2")
                     .soul(TheSoul)
-                    .filter(&|spectral| {
-                        !arcana_cards[..idx].contains(&Some(ArcanaCard::Spectral(*spectral)))
-                    })
+                    .filter(&|spectral| !arcana_cards.contains(&ArcanaCard::Spectral(*spectral)))
                     .dont_filter_on_showman()
                     .build()
                     .create(self)
@@ -168,25 +392,23 @@ impl RunData {
                     .type_key("Tarot")
                     .origin_key("ar1")
                     .soul(TheSoul)
-                    .filter(&|tarot| {
-                        !arcana_cards[..idx].contains(&Some(ArcanaCard::Tarot(*tarot)))
-                    })
+                    .filter(&|tarot| !arcana_cards.contains(&ArcanaCard::Tarot(*tarot)))
                     .dont_filter_on_showman()
                     .build()
                     .create(self)
             };
 
-            arcana_cards[idx] = Some(card);
+            arcana_cards.push(card);
         }
 
-        arcana_cards.map(Option::unwrap)
+        arcana_cards
     }
 
-    fn celestial<const N: usize>(&mut self) -> [CelestialCard; N] {
-        let mut celestial_cards = [None; N];
+    fn celestial(&mut self, count: usize) -> Vec<CelestialCard> {
+        let mut celestial_cards = Vec::with_capacity(count);
         let unlocked = self.planet_unlocked;
 
-        for idx in 0..N {
+        for idx in 0..count {
             let card = if self.vouchers[Voucher::Telescope as usize] && idx == 0 {
                 let highest_hand = HandType::iter()
                     .rev()
@@ -201,7 +423,7 @@ impl RunData {
                     .order(PLANET_ORDER)
                     .soul(BlackHole)
                     .filter(&|planet| {
-                        !celestial_cards[..idx].contains(&Some(CelestialCard::Planet(*planet)))
+                        !celestial_cards.contains(&CelestialCard::Planet(*planet))
                             && unlocked[*planet as usize]
                     })
                     .dont_filter_on_showman()
@@ -209,32 +431,48 @@ impl RunData {
                     .create(self)
             };
 
-            celestial_cards[idx] = Some(card);
+            celestial_cards.push(card);
         }
 
-        celestial_cards.map(Option::unwrap)
+        celestial_cards
     }
 
-    fn spectral<const N: usize>(&mut self) -> [Spectral; N] {
-        let mut spectral_cards = [None; N];
+    fn spectral(&mut self, count: usize) -> Vec<Spectral> {
+        let mut spectral_cards = Vec::with_capacity(count);
 
-        for idx in 0..N {
+        for _ in 0..count {
             let card = ConsumableCreator::<{ Spectral::COUNT }, Spectral, Spectral>::builder()
                 .type_key("Spectral")
                 .origin_key("spe")
                 .soul(TheSoul)
-                .filter(&|spectral| !spectral_cards[..idx].contains(&Some(*spectral)))
+                .filter(&|spectral| !spectral_cards.contains(spectral))
                 .dont_filter_on_showman()
                 .build()
                 .create(self);
 
-            spectral_cards[idx] = Some(card);
+            spectral_cards.push(card);
         }
 
-        spectral_cards.map(Option::unwrap)
+        spectral_cards
     }
 
-    fn normal<const N: usize>(&mut self) -> [Card; N] {
-        array::from_fn(|_| CardCreator::builder().origin_key("sta").build().create(self))
+    fn normal(&mut self, count: usize) -> Vec<Card> {
+        repeat_with(|| {
+            math::randomseed(self.rng.seed(&format!("stdset{}", self.ante)));
+            let enhanced = math::random() > 0.6;
+
+            let edition = self.poll_card_edition(&format!("standard_edition{}", self.ante), 2.);
+            let seal = self.poll_standard_seal();
+
+            CardCreator::builder()
+                .origin_key("sta")
+                .random_enhancement(enhanced)
+                .edition(edition)
+                .seal(seal)
+                .build()
+                .create(self)
+        })
+        .take(count)
+        .collect()
     }
 }
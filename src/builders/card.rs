@@ -4,6 +4,8 @@
     run::RunData,
     seeding::random_element,
 };
+use num_traits::FromPrimitive;
+use strum::EnumCount;
 use typed_builder::TypedBuilder;
 
 #[derive(TypedBuilder)]
@@ -18,11 +20,22 @@ pub struct CardCreator<'a> {
 
     #[builder(default=Seal::None)]
     seal: Seal,
+
+    /// Polls the enhancement from the `Enhanced` pool, overriding `enhancement`.
+    #[builder(default = false)]
+    random_enhancement: bool,
 }
 
 impl CardCreator<'_> {
     pub fn create(self, data: &mut RunData) -> Card {
-        let Self { origin_key, edition, enhancement, seal } = self;
+        let Self { origin_key, edition, mut enhancement, seal, random_enhancement } = self;
+
+        if random_enhancement {
+            let pool = [true; Enhancement::COUNT - 1];
+            let idx = data.poll(&pool, &format!("Enhanced{origin_key}{}", data.ante));
+            enhancement = Enhancement::from_usize(idx + 1).unwrap();
+        }
+
         let seed = data.rng.seed(&format!("front{origin_key}{}", data.ante));
 
         let mut card = random_element(&DEFAULT_CARDS, seed).clone();
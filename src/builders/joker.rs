@@ -46,8 +46,8 @@ pub struct JokerCreator<'a> {
     #[builder(setter(strip_bool(fallback = set_dont_filter_on_showman)))]
     dont_filter_on_showman: bool,
 
-    #[builder(default)]
-    sell_value: u32,
+    #[builder(default, setter(strip_option))]
+    sell_value: Option<u32>,
 
     #[builder(setter(strip_bool(fallback = set_debuffed)))]
     debuffed: bool,
@@ -55,6 +55,10 @@ pub struct JokerCreator<'a> {
     #[builder(default=JokerEdition::Base)]
     edition: JokerEdition,
 
+    /// Polls the edition with the `edi` key, overriding `edition`.
+    #[builder(setter(strip_bool(fallback = set_random_edition)))]
+    random_edition: bool,
+
     #[builder(default)]
     stickers: Stickers,
 
@@ -81,7 +85,8 @@ impl<'a> JokerCreator<'a> {
             joker_rarity,
             filter,
             dont_filter_on_showman,
-            edition,
+            mut edition,
+            random_edition,
             mut stickers,
             sell_value,
             debuffed,
@@ -128,14 +133,26 @@ impl<'a> JokerCreator<'a> {
             .collect_vec();
 
         let joker_type = data.poll(&available, &pool_key);
-        Joker {
+
+        if random_edition {
+            edition = data.poll_joker_edition(&format!("edi{origin_key}{}", data.ante));
+        }
+
+        let mut joker = Joker {
             joker_type: pool[joker_type],
             data: JokerInternalState::None,
             edition,
             stickers,
-            sell_value,
+            sell_value: 0,
             debuffed,
             dispatcher_order: DispatcherOrder::default(),
-        }
+        };
+
+        joker.sell_value = sell_value.unwrap_or_else(|| match joker.stickers.rental {
+            true => 1,
+            false => data.shop.sell_value(joker.base_cost()) as u32,
+        });
+
+        joker
     }
 }
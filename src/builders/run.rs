@@ -56,6 +56,7 @@ impl RunCreator {
             stake,
             joker_slots: 5,
             consumables: Vec::new(),
+            consumable_extra_value: Vec::new(),
             consumable_slots: 5,
             vouchers: [false; Voucher::COUNT],
             ante: 1,
@@ -70,8 +71,16 @@ impl RunCreator {
             ],
             times_boss_used: [0; _],
             this_antes_boss: BossBlindType::AmberAcorn,
+            boss_rerolled: false,
+            blind_on_deck: BlindType::Small,
             showman: false,
             shop: Shop::default(),
+            tags: Vec::new(),
+            ante_scaling: 1.,
+            money_per_hand: 1.,
+            money_per_discard: 0.,
+            interest_cap: 25.,
+            no_interest: false,
         };
 
         data.this_antes_boss = data.current_boss();
@@ -80,6 +89,11 @@ impl RunCreator {
             Red => data.starting_discards += 1,
             Blue => data.starting_hands += 1,
             Yellow => data.money += 10.,
+            Green => {
+                data.money_per_hand = 2.;
+                data.money_per_discard = 1.;
+                data.no_interest = true;
+            }
             Black => {
                 data.joker_slots += 1;
                 data.starting_hands -= 1;
@@ -116,9 +130,13 @@ impl RunCreator {
                 }
             }
             Abandoned => data.cards.retain(|card| !card.rank.is_face_card()),
+            Plasma => data.ante_scaling = 2.,
             _ => {}
         }
 
+        // The game rolls the first ante's voucher as the run starts
+        data.shop.vouchers = vec![data.poll_next_voucher()];
+
         Run { data, jokers: Vec::new(), game_state: GameState::Shop }
             .also_mut(|run| run.new_blind(BlindType::Small))
     }
@@ -1,11 +1,17 @@
-﻿use crate::card::{Enhancement::WildCard, Rank::*, Suit::*};
+﻿use crate::card::{
+    Enhancement::{StoneCard, WildCard},
+    Rank::*,
+    Suit::*,
+};
+use num_derive::FromPrimitive;
 use std::{
     cmp::Ordering,
     fmt::{Debug, Display, Formatter},
 };
 use strum::{EnumCount, EnumIter};
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Card {
     pub rank: Rank,
     pub suit: Suit,
@@ -40,7 +46,8 @@ impl Card {
 }
 
 #[repr(u8)]
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Ord, PartialOrd, EnumCount, EnumIter)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Ord, PartialOrd, EnumCount, EnumIter)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Rank {
     Rank2,
     Rank3,
@@ -82,6 +89,7 @@ pub static ALPHABETICAL_RANK_ORDER: [Rank; Rank::COUNT] =
 
 #[repr(u8)]
 #[derive(Debug, Clone, Copy, PartialEq, Hash, Eq, PartialOrd, Ord, EnumCount, EnumIter)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Suit {
     Spade,
     Heart,
@@ -109,8 +117,20 @@ pub struct MultiSuit {
     pub diamond: bool,
 }
 
+impl From<&Card> for MultiSuit {
+    fn from(card: &Card) -> Self {
+        Self {
+            spade: card.is_suit(Spade),
+            heart: card.is_suit(Heart),
+            club: card.is_suit(Club),
+            diamond: card.is_suit(Diamond),
+        }
+    }
+}
+
 #[repr(u8)]
-#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumCount, EnumIter)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumCount, EnumIter, FromPrimitive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Enhancement {
     None,
     BonusCard,
@@ -123,8 +143,26 @@ pub enum Enhancement {
     LuckyCard,
 }
 
+impl Enhancement {
+    #[must_use]
+    pub const fn game_name(&self) -> &'static str {
+        match self {
+            Enhancement::None => "c_base",
+            Enhancement::BonusCard => "m_bonus",
+            Enhancement::MultCard => "m_mult",
+            Enhancement::WildCard => "m_wild",
+            Enhancement::GlassCard => "m_glass",
+            Enhancement::SteelCard => "m_steel",
+            Enhancement::StoneCard => "m_stone",
+            Enhancement::GoldCard => "m_gold",
+            Enhancement::LuckyCard => "m_lucky",
+        }
+    }
+}
+
 #[repr(u8)]
-#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumCount, EnumIter)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumCount, EnumIter)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Edition {
     Base,
     Foil,
@@ -132,8 +170,20 @@ pub enum Edition {
     Polychrome,
 }
 
+impl Edition {
+    pub const fn extra_cost(&self) -> f64 {
+        match self {
+            Edition::Base => 0.,
+            Edition::Foil => 2.,
+            Edition::Holographic => 3.,
+            Edition::Polychrome => 5.,
+        }
+    }
+}
+
 #[repr(u8)]
-#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumCount, EnumIter)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, EnumCount, EnumIter)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Seal {
     None,
     Gold,
@@ -143,8 +193,13 @@ pub enum Seal {
 }
 
 impl Card {
-    pub(crate) fn is_suit(&self, suit: Suit) -> bool {
-        self.suit == suit || self.enhancement == WildCard
+    /// Stone Cards have no rank or suit.
+    pub fn has_rank(&self) -> bool {
+        self.enhancement != StoneCard
+    }
+
+    pub fn is_suit(&self, suit: Suit) -> bool {
+        self.has_rank() && (self.suit == suit || self.enhancement == WildCard)
     }
 }
 
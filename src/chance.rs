@@ -0,0 +1,145 @@
+//! Random effects during scoring, either rolled like the game or enumerated into a distribution.
+
+use crate::{
+    joker::{Joker, JokerType::OopsAll6s},
+    seeding::{BalatroRng, math},
+};
+use rand::{RngExt, SeedableRng, prelude::StdRng};
+
+/// Decides the outcome of random effects.
+pub trait Roller {
+    /// Whether an effect with probability `odds` happens.
+    fn chance(&mut self, rng: &mut BalatroRng, key: &str, odds: f64) -> bool;
+
+    /// A uniform integer in `min..=max`.
+    fn range(&mut self, rng: &mut BalatroRng, key: &str, min: u32, max: u32) -> u32;
+}
+
+/// Rolls with the game's `pseudorandom(key)`, advancing the run's rng.
+pub struct Seeded;
+
+impl Roller for Seeded {
+    fn chance(&mut self, rng: &mut BalatroRng, key: &str, odds: f64) -> bool {
+        math::randomseed(rng.seed(key));
+        math::random() < odds
+    }
+
+    fn range(&mut self, rng: &mut BalatroRng, key: &str, min: u32, max: u32) -> u32 {
+        math::randomseed(rng.seed(key));
+        min + math::random_idx((max - min + 1) as usize) as u32
+    }
+}
+
+/// `G.GAME.probabilities.normal`, doubled by every Oops! All 6s.
+pub fn probability_scale(jokers: &[Joker]) -> f64 {
+    2f64.powi(jokers.iter().filter(|joker| joker.joker_type == OopsAll6s).count() as i32)
+}
+
+/// The chance of a `1 in denominator` effect, like `1 in 4` for The Wheel of Fortune.
+pub fn odds(jokers: &[Joker], denominator: f64) -> f64 {
+    (probability_scale(jokers) / denominator).min(1.)
+}
+
+/// A [`Roller`] with the run's probability scale applied.
+pub struct Dice<'a> {
+    roller: &'a mut dyn Roller,
+    scale: f64,
+    /// Keys of the chances that hit, in order.
+    pub triggered: Vec<&'static str>,
+}
+
+impl<'a> Dice<'a> {
+    pub fn new(roller: &'a mut dyn Roller, jokers: &[Joker]) -> Self {
+        Dice { roller, scale: probability_scale(jokers), triggered: Vec::new() }
+    }
+
+    /// Rolls a `1 in denominator` chance.
+    pub fn one_in(&mut self, rng: &mut BalatroRng, key: &'static str, denominator: f64) -> bool {
+        let hit = self.roller.chance(rng, key, self.scale / denominator);
+        if hit {
+            self.triggered.push(key);
+        }
+
+        hit
+    }
+
+    pub fn range(&mut self, rng: &mut BalatroRng, key: &str, min: u32, max: u32) -> u32 {
+        self.roller.range(rng, key, min, max)
+    }
+}
+
+/// Walks every combination of outcomes, one scoring pass per combination.
+pub(crate) struct Enumerator {
+    /// The chosen option and the number of options of every roll so far.
+    path: Vec<(u32, u32)>,
+    next: usize,
+    pub(crate) probability: f64,
+}
+
+impl Enumerator {
+    pub(crate) fn new() -> Self {
+        Enumerator { path: Vec::new(), next: 0, probability: 1. }
+    }
+
+    fn choose(&mut self, options: u32) -> u32 {
+        if self.next == self.path.len() {
+            self.path.push((0, options));
+        }
+
+        self.next += 1;
+        self.path[self.next - 1].0
+    }
+
+    /// Prepares the next combination. Returns false once every combination was visited.
+    pub(crate) fn advance(&mut self) -> bool {
+        self.next = 0;
+        self.probability = 1.;
+
+        while let Some((choice, options)) = self.path.pop() {
+            if choice + 1 < options {
+                self.path.push((choice + 1, options));
+                return true;
+            }
+        }
+
+        false
+    }
+}
+
+impl Roller for Enumerator {
+    fn chance(&mut self, _: &mut BalatroRng, _: &str, odds: f64) -> bool {
+        if odds >= 1. || odds <= 0. {
+            return odds >= 1.;
+        }
+
+        let hit = self.choose(2) == 1;
+        self.probability *= if hit { odds } else { 1. - odds };
+        hit
+    }
+
+    fn range(&mut self, _: &mut BalatroRng, _: &str, min: u32, max: u32) -> u32 {
+        let options = max - min + 1;
+        self.probability /= options as f64;
+        min + self.choose(options)
+    }
+}
+
+/// Rolls every effect with its own rng, leaving the run's rng out of it.
+pub(crate) struct Sampler(StdRng);
+
+impl Sampler {
+    /// Always starts from the same state, so the same hand samples the same rolls.
+    pub(crate) fn new() -> Self {
+        Sampler(StdRng::seed_from_u64(0))
+    }
+}
+
+impl Roller for Sampler {
+    fn chance(&mut self, _: &mut BalatroRng, _: &str, odds: f64) -> bool {
+        self.0.random_bool(odds.clamp(0., 1.))
+    }
+
+    fn range(&mut self, _: &mut BalatroRng, _: &str, min: u32, max: u32) -> u32 {
+        self.0.random_range(min..=max)
+    }
+}
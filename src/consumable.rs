@@ -1,16 +1,22 @@
-﻿use crate::hands::{
-    HandType,
-    HandType::{
-        FiveOfAKind, Flush, FlushFive, FlushHouse, FourOfAKind, FullHouse, HighCard, Pair,
-        Straight, StraightFlush, ThreeOfAKind, TwoPair,
+﻿use crate::{
+    blind::Blind,
+    hands::{
+        HandType,
+        HandType::{
+            FiveOfAKind, Flush, FlushFive, FlushHouse, FourOfAKind, FullHouse, HighCard, Pair,
+            Straight, StraightFlush, ThreeOfAKind, TwoPair,
+        },
     },
+    run::RunData,
+    vouchers::Voucher::Observatory,
 };
 use derive_more::From;
 use num_derive::FromPrimitive;
 use std::fmt::{Display, Formatter};
-use strum::{Display, EnumCount};
+use strum::{Display, EnumCount, EnumIter};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, EnumCount, From)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Consumable {
     TarotCard(Tarot),
     PlanetCard(Planet),
@@ -27,8 +33,85 @@ impl Display for Consumable {
     }
 }
 
+impl Consumable {
+    pub const fn base_cost(&self) -> f64 {
+        match self {
+            Consumable::TarotCard(_) | Consumable::PlanetCard(_) => 3.,
+            Consumable::SpectralCard(_) => 4.,
+        }
+    }
+
+    pub fn scored(&self, data: &RunData, blind: &mut Blind, hand_type: HandType) {
+        if let Consumable::PlanetCard(planet) = self
+            && *planet == hand_type
+            && data.vouchers[Observatory as usize]
+        {
+            blind.mult *= 1.5;
+        }
+    }
+
+    #[must_use]
+    pub const fn game_name(&self) -> &'static str {
+        match self {
+            Consumable::TarotCard(Tarot::TheFool) => "c_fool",
+            Consumable::TarotCard(Tarot::TheMagician) => "c_magician",
+            Consumable::TarotCard(Tarot::TheHighPriestess) => "c_high_priestess",
+            Consumable::TarotCard(Tarot::TheEmpress) => "c_empress",
+            Consumable::TarotCard(Tarot::TheEmperor) => "c_emperor",
+            Consumable::TarotCard(Tarot::TheHierophant) => "c_heirophant",
+            Consumable::TarotCard(Tarot::TheLovers) => "c_lovers",
+            Consumable::TarotCard(Tarot::TheChariot) => "c_chariot",
+            Consumable::TarotCard(Tarot::Justice) => "c_justice",
+            Consumable::TarotCard(Tarot::TheHermit) => "c_hermit",
+            Consumable::TarotCard(Tarot::TheWheelOfFortune) => "c_wheel_of_fortune",
+            Consumable::TarotCard(Tarot::Strength) => "c_strength",
+            Consumable::TarotCard(Tarot::TheHangedMan) => "c_hanged_man",
+            Consumable::TarotCard(Tarot::Death) => "c_death",
+            Consumable::TarotCard(Tarot::Temperance) => "c_temperance",
+            Consumable::TarotCard(Tarot::TheDevil) => "c_devil",
+            Consumable::TarotCard(Tarot::TheTower) => "c_tower",
+            Consumable::TarotCard(Tarot::TheStar) => "c_star",
+            Consumable::TarotCard(Tarot::TheMoon) => "c_moon",
+            Consumable::TarotCard(Tarot::TheSun) => "c_sun",
+            Consumable::TarotCard(Tarot::Judgement) => "c_judgement",
+            Consumable::TarotCard(Tarot::TheWorld) => "c_world",
+            Consumable::PlanetCard(HighCard) => "c_pluto",
+            Consumable::PlanetCard(Pair) => "c_mercury",
+            Consumable::PlanetCard(TwoPair) => "c_uranus",
+            Consumable::PlanetCard(ThreeOfAKind) => "c_venus",
+            Consumable::PlanetCard(Straight) => "c_saturn",
+            Consumable::PlanetCard(Flush) => "c_jupiter",
+            Consumable::PlanetCard(FullHouse) => "c_earth",
+            Consumable::PlanetCard(FourOfAKind) => "c_mars",
+            Consumable::PlanetCard(StraightFlush) => "c_neptune",
+            Consumable::PlanetCard(FiveOfAKind) => "c_planet_x",
+            Consumable::PlanetCard(FlushHouse) => "c_ceres",
+            Consumable::PlanetCard(FlushFive) => "c_eris",
+            Consumable::SpectralCard(Spectral::Familiar) => "c_familiar",
+            Consumable::SpectralCard(Spectral::Grim) => "c_grim",
+            Consumable::SpectralCard(Spectral::Incantation) => "c_incantation",
+            Consumable::SpectralCard(Spectral::Talisman) => "c_talisman",
+            Consumable::SpectralCard(Spectral::Aura) => "c_aura",
+            Consumable::SpectralCard(Spectral::Wraith) => "c_wraith",
+            Consumable::SpectralCard(Spectral::Sigil) => "c_sigil",
+            Consumable::SpectralCard(Spectral::Ouija) => "c_ouija",
+            Consumable::SpectralCard(Spectral::Ectoplasm) => "c_ectoplasm",
+            Consumable::SpectralCard(Spectral::Immolate) => "c_immolate",
+            Consumable::SpectralCard(Spectral::Ankh) => "c_ankh",
+            Consumable::SpectralCard(Spectral::DejaVu) => "c_deja_vu",
+            Consumable::SpectralCard(Spectral::Hex) => "c_hex",
+            Consumable::SpectralCard(Spectral::Trance) => "c_trance",
+            Consumable::SpectralCard(Spectral::Medium) => "c_medium",
+            Consumable::SpectralCard(Spectral::Cryptid) => "c_cryptid",
+            Consumable::SpectralCard(Spectral::TheSoul) => "c_soul",
+            Consumable::SpectralCard(Spectral::BlackHole) => "c_black_hole",
+        }
+    }
+}
+
 #[repr(u8)]
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Display, EnumCount, FromPrimitive)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display, EnumCount, EnumIter, FromPrimitive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Tarot {
     TheFool,
     TheMagician,
@@ -82,7 +165,8 @@ impl Planet {
 }
 
 #[repr(u8)]
-#[derive(Debug, Display, Clone, Copy, PartialEq, Eq, EnumCount, FromPrimitive)]
+#[derive(Debug, Display, Clone, Copy, PartialEq, Eq, EnumCount, EnumIter, FromPrimitive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Spectral {
     Familiar,
     Grim,
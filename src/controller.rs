@@ -1,39 +1,160 @@
-﻿use crate::{blind::Blind, run::RunData};
+﻿use crate::{
+    blind::Blind,
+    boosters::{BoosterPack, PickError},
+    hands::HandType,
+    observation::{BlindView, Observation, ShopView},
+};
+use std::{
+    error::Error,
+    fmt::{Display, Formatter},
+};
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ShopAction {
+    BuyItem(usize),
+    BuyVoucher(usize),
+    Reroll,
+    SellJoker(usize),
+    SellConsumable(usize),
+    /// Buys and opens a pack, as an index into `ShopView::packs`.
+    BuyPack(usize),
     ExitShop,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PackAction {
+    Pick(usize),
+    /// Closes the pack, giving up the picks that are left.
+    Skip,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum BlindSelectionAction {
     PlayBlind,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum BlindAction {
     SelectCard(usize),
     Play,
     Discard,
     Abort,
 }
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum CashoutAction {
     ReturnToShop,
 }
 
+/// Why an action was rejected. The run is left as it was before the action.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ActionError {
+    /// There's no held card, shop item, voucher, pack, joker or consumable at this index.
+    OutOfRange(usize),
+    /// The held card is already selected, cards can't be deselected.
+    Deselection(usize),
+    /// Five cards are already selected.
+    SelectionFull,
+    NothingSelected,
+    NotEnoughMoney,
+    NoRoom,
+    NoPicksLeft,
+    /// Eternal jokers can't be sold.
+    Eternal,
+    NoHands,
+    NoDiscards,
+    /// The boss blind won't let this hand score.
+    HandNotAllowed(HandType),
+}
+
+impl Display for ActionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ActionError::OutOfRange(idx) => write!(f, "nothing at index {idx}"),
+            ActionError::Deselection(idx) => write!(f, "card {idx} is already selected"),
+            ActionError::SelectionFull => write!(f, "five cards are already selected"),
+            ActionError::NothingSelected => write!(f, "no cards are selected"),
+            ActionError::NotEnoughMoney => write!(f, "not enough money"),
+            ActionError::NoRoom => write!(f, "no free slot"),
+            ActionError::NoPicksLeft => write!(f, "no picks left in this pack"),
+            ActionError::Eternal => write!(f, "eternal jokers can't be sold"),
+            ActionError::NoHands => write!(f, "no hands left"),
+            ActionError::NoDiscards => write!(f, "no discards left"),
+            ActionError::HandNotAllowed(hand_type) => write!(f, "{hand_type:?} isn't allowed"),
+        }
+    }
+}
+
+impl Error for ActionError {}
+
+impl From<PickError> for ActionError {
+    fn from(error: PickError) -> Self {
+        match error {
+            PickError::NoPicksLeft => ActionError::NoPicksLeft,
+            PickError::OutOfRange(idx) => ActionError::OutOfRange(idx),
+        }
+    }
+}
+
+/// Decides what to do at every step of [`Run::simulate`](crate::run::Run::simulate).
+///
+/// Controllers only see what a player would and answer with actions, the engine applies them.
 pub trait Controller {
-    fn shop(&mut self) -> Vec<ShopAction> {
+    fn shop(&mut self, _: &Observation, _: &ShopView) -> Vec<ShopAction> {
         vec![ShopAction::ExitShop]
     }
 
-    fn blind_selection(&mut self) -> BlindSelectionAction {
+    fn blind_selection(&mut self, _: &Observation) -> BlindSelectionAction {
         BlindSelectionAction::PlayBlind
     }
 
-    fn blind(&mut self, blind: &mut Blind, data: &mut RunData) -> Vec<BlindAction>;
-    fn cashout(&mut self) -> CashoutAction {
+    /// Called right after a pack is bought in the shop.
+    fn pack(&mut self, _: &Observation, _: &BoosterPack) -> Vec<PackAction> {
+        vec![PackAction::Skip]
+    }
+
+    fn blind(&mut self, observation: &Observation, blind: &BlindView) -> Vec<BlindAction>;
+    fn cashout(&mut self, _: &Observation) -> CashoutAction {
         CashoutAction::ReturnToShop
     }
+
+    /// Called for every returned action the engine couldn't apply.
+    fn rejected(&mut self, _: ActionError) {}
+}
+
+impl<C: Controller + ?Sized> Controller for &mut C {
+    fn shop(&mut self, observation: &Observation, shop: &ShopView) -> Vec<ShopAction> {
+        (**self).shop(observation, shop)
+    }
+
+    fn blind_selection(&mut self, observation: &Observation) -> BlindSelectionAction {
+        (**self).blind_selection(observation)
+    }
+
+    fn pack(&mut self, observation: &Observation, pack: &BoosterPack) -> Vec<PackAction> {
+        (**self).pack(observation, pack)
+    }
+
+    fn blind(&mut self, observation: &Observation, blind: &BlindView) -> Vec<BlindAction> {
+        (**self).blind(observation, blind)
+    }
+
+    fn cashout(&mut self, observation: &Observation) -> CashoutAction {
+        (**self).cashout(observation)
+    }
+
+    fn rejected(&mut self, error: ActionError) {
+        (**self).rejected(error)
+    }
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum SimulationResult {
     Lost { blind: Blind },
     Aborted,
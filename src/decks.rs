@@ -1,6 +1,10 @@
 ﻿use crate::{
+    blind::{Blind, BlindType, BlindType::Boss},
     card::{ALPHABETICAL_RANK_ORDER, ALPHABETICAL_SUIT_ORDER, Card},
+    decks::DeckType::{Anaglyph, Plasma},
     misc::UnpackedMap,
+    run::RunData,
+    tags::Tag::DoubleTag,
 };
 use itertools::Itertools;
 use std::sync::LazyLock;
@@ -21,6 +25,7 @@ pub struct Deck {
 
 #[repr(u8)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, EnumCount, EnumIter)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum DeckType {
     Red,
     Blue,
@@ -47,3 +52,41 @@ impl Deck {
         vec
     }
 }
+
+impl DeckType {
+    #[must_use]
+    pub const fn game_name(&self) -> &'static str {
+        match self {
+            DeckType::Red => "b_red",
+            DeckType::Blue => "b_blue",
+            DeckType::Yellow => "b_yellow",
+            DeckType::Green => "b_green",
+            DeckType::Black => "b_black",
+            DeckType::Magic => "b_magic",
+            DeckType::Nebula => "b_nebula",
+            DeckType::Ghost => "b_ghost",
+            DeckType::Abandoned => "b_abandoned",
+            DeckType::Checkered => "b_checkered",
+            DeckType::Zodiac => "b_zodiac",
+            DeckType::Painted => "b_painted",
+            DeckType::Anaglyph => "b_anaglyph",
+            DeckType::Plasma => "b_plasma",
+            DeckType::Erratic => "b_erratic",
+        }
+    }
+
+    /// Runs after every joker and consumable was scored, right before `chips * mult` is added.
+    pub fn final_scoring_step(&self, blind: &mut Blind) {
+        if *self == Plasma {
+            let balanced = ((blind.chips + blind.mult) / 2.).floor();
+            blind.chips = balanced;
+            blind.mult = balanced;
+        }
+    }
+
+    pub fn blind_defeated(self, data: &mut RunData, blind_type: BlindType) {
+        if let (Anaglyph, Boss(_)) = (self, blind_type) {
+            data.tags.push(DoubleTag);
+        }
+    }
+}
@@ -0,0 +1,145 @@
+//! The odds of drawing into a hand, knowing what's left in the deck but not in which order.
+
+use crate::{
+    blind::Blind,
+    card::{Card, Enhancement::WildCard, Rank, Suit},
+    run::RunData,
+};
+use itertools::Itertools;
+use strum::EnumCount;
+
+/// The cards left in the deck as the deck view shows them.
+///
+/// Only the held cards and the draws are counted, jokers like Four Fingers or Shortcut aren't.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Outs {
+    /// Sorted, so the shuffle can't be recovered.
+    pub remaining: Vec<Card>,
+}
+
+impl Outs {
+    pub fn new(blind: &Blind, data: &RunData) -> Self {
+        let mut remaining = blind.cards.iter().map(|&idx| data.cards[idx].clone()).collect_vec();
+        remaining.sort();
+
+        Self { remaining }
+    }
+
+    /// The chance that the next `draws` cards contain at least `needed` cards matching `out`.
+    pub fn at_least(&self, draws: usize, needed: usize, out: impl Fn(&Card) -> bool) -> f64 {
+        self.probability(draws, 2, |card| out(card) as usize, |drawn| drawn[1] >= needed)
+    }
+
+    /// The chance that `held` and the next `draws` cards have five cards of one suit. Wild Cards
+    /// count for every suit.
+    pub fn flush(&self, held: &[&Card], draws: usize) -> f64 {
+        let mut have = [0; SUITS];
+        for card in held {
+            have[suit(card)] += 1;
+        }
+
+        self.probability(draws, SUITS, suit, |drawn| {
+            let wild = drawn[WILD] + have[WILD];
+            (0..Suit::COUNT).any(|suit| drawn[suit] + have[suit] + wild >= 5)
+        })
+    }
+
+    /// The chance that `held` and the next `draws` cards have five consecutive ranks.
+    pub fn straight(&self, held: &[&Card], draws: usize) -> f64 {
+        let mut have = [false; RANKS];
+        for card in held {
+            have[rank(card)] = true;
+        }
+
+        self.probability(draws, RANKS, rank, |drawn| {
+            let present = |rank: usize| have[rank] || drawn[rank] > 0;
+            // Aces also start the straight from 2 to 5
+            let ace = Rank::COUNT - 1;
+            let ranks = [ace].into_iter().chain(0..Rank::COUNT).collect_vec();
+            ranks.windows(5).any(|window| window.iter().all(|&rank| present(rank)))
+        })
+    }
+
+    /// The chance that `held` and the next `draws` cards have `size` cards of one rank, like 3 for
+    /// a Three of a Kind.
+    pub fn of_a_kind(&self, held: &[&Card], size: usize, draws: usize) -> f64 {
+        let mut have = [0; RANKS];
+        for card in held {
+            have[rank(card)] += 1;
+        }
+
+        self.probability(draws, RANKS, rank, |drawn| {
+            (0..Rank::COUNT).any(|rank| drawn[rank] + have[rank] >= size)
+        })
+    }
+
+    /// Sums the chances of every way to draw `draws` cards, counted per category, that `hit`
+    /// accepts. Every draw order is equally likely.
+    fn probability(
+        &self,
+        draws: usize,
+        categories: usize,
+        category: impl Fn(&Card) -> usize,
+        hit: impl Fn(&[usize]) -> bool,
+    ) -> f64 {
+        let mut available = vec![0; categories];
+        for card in &self.remaining {
+            available[category(card)] += 1;
+        }
+
+        let draws = draws.min(self.remaining.len());
+        let mut drawn = vec![0; categories];
+        let ways = count_draws(&available, &mut drawn, 0, draws, &hit);
+
+        ways / binomial(self.remaining.len(), draws)
+    }
+}
+
+/// Suit categories, then Wild Cards, then Stone Cards which have no suit.
+const SUITS: usize = Suit::COUNT + 2;
+const WILD: usize = Suit::COUNT;
+/// Rank categories, then Stone Cards which have no rank.
+const RANKS: usize = Rank::COUNT + 1;
+
+fn suit(card: &Card) -> usize {
+    match card {
+        card if !card.has_rank() => WILD + 1,
+        card if card.enhancement == WildCard => WILD,
+        card => card.suit as usize,
+    }
+}
+
+fn rank(card: &Card) -> usize {
+    if card.has_rank() { card.rank as usize } else { Rank::COUNT }
+}
+
+/// The number of ways to draw `left` more cards from the categories starting at `category`.
+fn count_draws(
+    available: &[usize],
+    drawn: &mut [usize],
+    category: usize,
+    left: usize,
+    hit: &impl Fn(&[usize]) -> bool,
+) -> f64 {
+    if category == available.len() {
+        return if left == 0 && hit(drawn) { 1. } else { 0. };
+    }
+
+    if available[category..].iter().sum::<usize>() < left {
+        return 0.;
+    }
+
+    let mut ways = 0.;
+    for count in 0..=left.min(available[category]) {
+        drawn[category] = count;
+        ways += binomial(available[category], count)
+            * count_draws(available, drawn, category + 1, left - count, hit);
+    }
+
+    drawn[category] = 0;
+    ways
+}
+
+fn binomial(n: usize, k: usize) -> f64 {
+    (0..k).fold(1., |ways, i| ways * (n - i) as f64 / (i + 1) as f64)
+}
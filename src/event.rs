@@ -3,6 +3,7 @@
 #[derive(Debug, Clone, PartialEq, Eq, EnumCount)]
 pub enum Event {
     BlindEntered,
+    RoundEnded,
     Scored,
     CardScored,
     CardScoredSpade,
@@ -12,6 +13,7 @@ pub enum Event {
 }
 
 #[derive(Default, PartialEq, Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DispatcherOrder {
     pub events: [i64; Event::COUNT],
 }
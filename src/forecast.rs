@@ -0,0 +1,86 @@
+use crate::{
+    blind::{Blind, BlindType, BossBlindType},
+    boosters::BoosterPackType,
+    run::Run,
+    shop::ShopItem,
+    tags::Tag,
+    vouchers::Voucher,
+};
+use std::array;
+
+/// A shop as it would be generated if nothing is bought.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShopForecast {
+    /// The blind beaten right before this shop.
+    pub after: BlindType,
+    /// The initial inventory followed by the inventory after each reroll.
+    pub inventories: Vec<Vec<ShopItem>>,
+    pub packs: [BoosterPackType; 2],
+    /// The shop after a Boss already offers the next ante's voucher.
+    pub vouchers: Vec<Voucher>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnteForecast {
+    pub ante: i32,
+    /// `None` if the forecast started after this ante's Small Blind, once its tags were rolled.
+    pub tags: Option<[Tag; 2]>,
+    pub boss: BossBlindType,
+    pub shops: Vec<ShopForecast>,
+}
+
+/// Yields one [`AnteForecast`] per ante, starting at the run's current blind.
+///
+/// Works on a copy of the run, so the original and its rng state are left untouched.
+/// Every blind is assumed to be beaten without skipping, buying or opening packs.
+pub struct Forecaster {
+    run: Run,
+    rerolls: usize,
+}
+
+impl Forecaster {
+    #[must_use]
+    pub fn new(run: &Run, rerolls: usize) -> Self {
+        Self { run: run.fork(), rerolls }
+    }
+
+    fn next_shop(&mut self) -> ShopForecast {
+        let run = &mut self.run;
+        let after = run.data.blind_on_deck;
+
+        run.data.end_round(&Blind { blind_type: after, ..Default::default() });
+        run.enter_shop(matches!(after, BlindType::Boss(_)));
+
+        let mut inventories = vec![run.data.shop.inventory.clone()];
+        for _ in 0..self.rerolls {
+            run.regenerate_shop_inventory();
+            inventories.push(run.data.shop.inventory.clone());
+        }
+
+        ShopForecast {
+            after,
+            inventories,
+            packs: run.data.shop.packs.map(Option::unwrap),
+            vouchers: run.data.shop.vouchers.clone(),
+        }
+    }
+}
+
+impl Iterator for Forecaster {
+    type Item = AnteForecast;
+
+    fn next(&mut self) -> Option<AnteForecast> {
+        let data = &mut self.run.data;
+        let ante = data.ante;
+        let tags = (data.blind_on_deck == BlindType::Small)
+            .then(|| array::from_fn(|_| data.next_random_tag()));
+        let boss = data.this_antes_boss;
+
+        let mut shops = Vec::new();
+        while self.run.data.ante == ante {
+            shops.push(self.next_shop());
+        }
+
+        Some(AnteForecast { ante, tags, boss, shops })
+    }
+}
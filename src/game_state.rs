@@ -1,9 +1,10 @@
 ﻿use crate::blind::Blind;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum GameState {
     Shop,
     BlindSelection,
     Blind(Blind),
-    CashOut,
+    CashOut { earnings: f64 },
 }
@@ -2,14 +2,17 @@
     card::{
         Card, Rank,
         Rank::{Ace, Rank2, Rank3, Rank4, Rank5},
+        Suit,
     },
     hands::HandType::*,
 };
+use itertools::Itertools;
 use num_derive::FromPrimitive;
 use std::mem::MaybeUninit;
 use strum::{EnumCount, EnumIter, IntoEnumIterator};
 
 #[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Hand {
     pub cards: [usize; 5],
     pub len: usize,
@@ -25,15 +28,25 @@ impl Hand {
     pub fn resolve<'a>(&self, cards: &'a [Card]) -> ResolvedHand<'a> {
         ResolvedHand(self.card_slice().iter().map(|idx| &cards[*idx]).collect())
     }
+
+    /// The played cards that score: the ones making up the hand and every Stone Card, or all of
+    /// them with Splash. Indices into `cards`, in played order.
+    pub fn scoring(&self, cards: &[Card], splash: bool) -> Vec<usize> {
+        let resolved = self.resolve(cards);
+        let scoring = resolved.scoring(resolved.hand_type());
+
+        self.card_slice()
+            .iter()
+            .enumerate()
+            .filter(|&(pos, &idx)| splash || !cards[idx].has_rank() || scoring.contains(&pos))
+            .map(|(_, &idx)| idx)
+            .collect()
+    }
 }
 
 impl ResolvedHand<'_> {
     pub fn contains(&self, hand_type: HandType) -> bool {
-        let mut rank_counts = [0; Rank::COUNT];
-
-        for card in &self.0 {
-            rank_counts[card.rank as usize] += 1;
-        }
+        let rank_counts = self.rank_counts();
 
         match hand_type {
             HighCard => true,
@@ -41,7 +54,7 @@ impl ResolvedHand<'_> {
             TwoPair => rank_counts.into_iter().filter(|count| *count >= 2).count() >= 2,
             ThreeOfAKind => rank_counts.into_iter().any(|count| count >= 3),
             Straight => {
-                if self.0.len() < 5 {
+                if self.0.len() < 5 || self.0.iter().any(|card| !card.has_rank()) {
                     return false;
                 }
 
@@ -55,12 +68,12 @@ impl ResolvedHand<'_> {
                     return true;
                 }
 
-                ranks[0] as u8 - 1 == ranks[1] as u8
-                    && ranks[1] as u8 - 1 == ranks[2] as u8
-                    && ranks[2] as u8 - 1 == ranks[3] as u8
-                    && ranks[3] as u8 - 1 == ranks[4] as u8
+                ranks.windows(2).all(|pair| pair[0] as u8 + 1 == pair[1] as u8)
+            }
+            Flush => {
+                self.0.len() == 5
+                    && Suit::iter().any(|suit| self.0.iter().all(|card| card.is_suit(suit)))
             }
-            Flush => self.0.len() == 5 && self.0.iter().all(|card| card.suit == self.0[0].suit),
             FullHouse => rank_counts.contains(&3) && rank_counts.contains(&2),
             FourOfAKind => rank_counts.contains(&4),
             FiveOfAKind => rank_counts.contains(&5),
@@ -70,6 +83,42 @@ impl ResolvedHand<'_> {
         }
     }
 
+    fn rank_counts(&self) -> [u32; Rank::COUNT] {
+        let mut rank_counts = [0; Rank::COUNT];
+
+        for card in self.0.iter().filter(|card| card.has_rank()) {
+            rank_counts[card.rank as usize] += 1;
+        }
+
+        rank_counts
+    }
+
+    /// Positions of the cards making up `hand_type`, without Stone Cards.
+    pub fn scoring(&self, hand_type: HandType) -> Vec<usize> {
+        let rank_counts = self.rank_counts();
+        let positions = |min_count: u32| {
+            self.0
+                .iter()
+                .positions(|card| card.has_rank() && rank_counts[card.rank as usize] >= min_count)
+                .collect()
+        };
+
+        match hand_type {
+            HighCard => self
+                .0
+                .iter()
+                .enumerate()
+                .filter(|(_, card)| card.has_rank())
+                .max_by_key(|(_, card)| card.rank)
+                .map(|(pos, _)| vec![pos])
+                .unwrap_or_default(),
+            Pair | TwoPair => positions(2),
+            ThreeOfAKind => positions(3),
+            FourOfAKind => positions(4),
+            _ => positions(1),
+        }
+    }
+
     pub fn ranks(&self) -> impl Iterator<Item = u8> {
         self.0.iter().map(|x| x.rank as _)
     }
@@ -87,6 +136,7 @@ impl ResolvedHand<'_> {
 
 #[repr(u8)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, EnumCount, EnumIter, FromPrimitive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum HandType {
     HighCard,
     Pair,
@@ -141,4 +191,49 @@ impl HandType {
             FlushFive => 16,
         })
     }
+
+    /// The chips a level adds to the hand.
+    #[must_use]
+    pub const fn level_chips(&self) -> u64 {
+        match self {
+            HighCard => 10,
+            Pair | Flush => 15,
+            TwoPair | ThreeOfAKind => 20,
+            FullHouse => 25,
+            Straight | FourOfAKind => 30,
+            FiveOfAKind => 35,
+            StraightFlush | FlushHouse => 40,
+            FlushFive => 50,
+        }
+    }
+
+    /// The mult a level adds to the hand.
+    #[must_use]
+    pub const fn level_mult(&self) -> u64 {
+        match self {
+            HighCard | Pair | TwoPair => 1,
+            ThreeOfAKind | Flush | FullHouse => 2,
+            Straight | FourOfAKind | FiveOfAKind | FlushFive => 3,
+            StraightFlush | FlushHouse => 4,
+        }
+    }
+
+    /// The key of `G.GAME.hands`.
+    #[must_use]
+    pub const fn game_name(&self) -> &'static str {
+        match self {
+            HighCard => "High Card",
+            Pair => "Pair",
+            TwoPair => "Two Pair",
+            ThreeOfAKind => "Three of a Kind",
+            Straight => "Straight",
+            Flush => "Flush",
+            FullHouse => "Full House",
+            FourOfAKind => "Four of a Kind",
+            StraightFlush => "Straight Flush",
+            FiveOfAKind => "Five of a Kind",
+            FlushHouse => "Flush House",
+            FlushFive => "Flush Five",
+        }
+    }
 }
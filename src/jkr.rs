@@ -0,0 +1,1420 @@
+//! Reading and writing the game's `save.jkr` format: a raw deflate compressed
+//! `return { ... }` Lua table.
+
+use crate::{
+    blind::{Blind, BlindType, BossBlindType},
+    boosters::{BoosterPackSize, BoosterPackType},
+    card::{Card, Edition, Enhancement, Rank, Seal, Suit},
+    consumable::{Consumable, Spectral, Tarot},
+    decks::DeckType,
+    event::DispatcherOrder,
+    game_state::GameState,
+    hands::{Hand, HandType},
+    joker::{Joker, JokerEdition, JokerInternalState, JokerType, Stickers},
+    run::{Run, RunData},
+    seeding::{BalatroRng, math::LUA},
+    shop::{Shop, ShopItem},
+    stake::Stake,
+    tags::Tag,
+    vouchers::Voucher,
+};
+use flate2::{Compression, read::DeflateDecoder, write::DeflateEncoder};
+use itertools::Itertools;
+use mlua::{Table, Value};
+use num_traits::FromPrimitive;
+use std::{
+    cell::Cell,
+    cmp::Ordering,
+    error::Error,
+    fmt::{Debug, Display, Formatter, Write as _},
+    io::{Read, Write},
+    path::Path,
+};
+use strum::{EnumCount, IntoEnumIterator};
+
+/// `G.STATES` values a run can be saved in.
+const SELECTING_HAND: i64 = 1;
+const SHOP: i64 = 5;
+const BLIND_SELECT: i64 = 7;
+const ROUND_EVAL: i64 = 8;
+
+#[derive(Debug)]
+pub enum JkrError {
+    Io(std::io::Error),
+    Lua(mlua::Error),
+    /// A center, tag or other game key this crate doesn't know.
+    UnknownKey(String),
+    /// Saves made outside the blind selection, a blind or the shop can't be resumed.
+    UnsupportedState(i64),
+}
+
+impl Display for JkrError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            JkrError::Io(err) => write!(f, "could not read save: {err}"),
+            JkrError::Lua(err) => write!(f, "malformed save: {err}"),
+            JkrError::UnknownKey(key) => write!(f, "unknown game key `{key}`"),
+            JkrError::UnsupportedState(state) => write!(f, "unsupported game state {state}"),
+        }
+    }
+}
+
+impl Error for JkrError {}
+
+impl From<std::io::Error> for JkrError {
+    fn from(err: std::io::Error) -> Self {
+        JkrError::Io(err)
+    }
+}
+
+impl From<mlua::Error> for JkrError {
+    fn from(err: mlua::Error) -> Self {
+        JkrError::Lua(err)
+    }
+}
+
+type Result<T> = std::result::Result<T, JkrError>;
+
+pub fn decompress(bytes: &[u8]) -> Result<String> {
+    let mut text = String::new();
+    DeflateDecoder::new(bytes).read_to_string(&mut text)?;
+    Ok(text)
+}
+
+/// The game compresses its saves at level 1.
+pub fn compress(text: &str) -> Vec<u8> {
+    let mut encoder = DeflateEncoder::new(Vec::new(), Compression::new(1));
+    encoder.write_all(text.as_bytes()).expect("writing to a Vec can't fail");
+    encoder.finish().expect("writing to a Vec can't fail")
+}
+
+impl Run {
+    pub fn load_jkr(path: impl AsRef<Path>) -> Result<Run> {
+        Run::from_jkr(&std::fs::read(path)?)
+    }
+
+    pub fn from_jkr(bytes: &[u8]) -> Result<Run> {
+        Run::from_jkr_table(&decompress(bytes)?)
+    }
+
+    /// Parses the decompressed `return { ... }` table. It runs without any globals.
+    pub fn from_jkr_table(text: &str) -> Result<Run> {
+        LUA.with(|lua| {
+            let save: Table = lua.load(text).set_environment(lua.create_table()?).eval()?;
+            parse_run(&save)
+        })
+    }
+
+    pub fn save_jkr(&self, path: impl AsRef<Path>) -> Result<()> {
+        Ok(std::fs::write(path, self.to_jkr())?)
+    }
+
+    pub fn to_jkr(&self) -> Vec<u8> {
+        compress(&self.to_jkr_table())
+    }
+
+    /// Writes the run into a fresh save with every field `save_run` writes: full
+    /// `Card:save()` tables, `G.GAME` and the card areas.
+    pub fn to_jkr_table(&self) -> String {
+        self.patch_jkr_table(TEMPLATE).expect("the save template is a valid table")
+    }
+
+    /// Writes the run over an existing save, keeping what this crate doesn't model, like
+    /// `round_scores`, from the save it was loaded from.
+    pub fn patch_jkr(&self, save: &[u8]) -> Result<Vec<u8>> {
+        Ok(compress(&self.patch_jkr_table(&decompress(save)?)?))
+    }
+
+    pub fn patch_jkr_table(&self, save: &str) -> Result<String> {
+        LUA.with(|lua| {
+            let save: Table = lua.load(save).set_environment(lua.create_table()?).eval()?;
+            let env = lua.create_table()?;
+            env.set("pairs", lua.globals().get::<Value>("pairs")?)?;
+            let patch: Table = lua.load(write_run(self)).set_environment(env).eval()?;
+            merge(&save, &patch)?;
+
+            let resets: Table = save.get::<Table>("GAME")?.get("round_resets")?;
+            resets.set("temp_reroll_cost", self.data.shop.temp_base_reroll_price)?;
+
+            let mut text = String::from("return ");
+            pack(&save, &mut text)?;
+            Ok(text)
+        })
+    }
+}
+
+fn lookup<T: IntoEnumIterator>(key: &str, game_name: impl Fn(&T) -> &'static str) -> Result<T> {
+    T::iter().find(|value| game_name(value) == key).ok_or_else(|| unknown(key))
+}
+
+fn unknown(key: &str) -> JkrError {
+    JkrError::UnknownKey(key.to_string())
+}
+
+fn parse_run(save: &Table) -> Result<Run> {
+    let game: Table = save.get("GAME")?;
+    let areas: Table = save.get("cardAreas")?;
+    let round_resets: Table = game.get("round_resets")?;
+    let current_round: Table = game.get("current_round")?;
+
+    let mut playing_cards = Vec::new();
+    let mut area_sort_ids = Vec::new();
+    let mut face_down = Vec::new();
+    for area in ["deck", "hand", "discard", "play"] {
+        let tables = area_cards(&areas, area)?;
+        let cards = tables.iter().map(parse_card).try_collect::<_, Vec<_>, _>()?;
+        for (table, (sort_id, _)) in tables.iter().zip(&cards) {
+            if area == "hand" && table.get::<Option<String>>("facing")?.as_deref() == Some("back") {
+                face_down.push(*sort_id);
+            }
+        }
+
+        area_sort_ids.push(cards.iter().map(|(sort_id, _)| *sort_id).collect_vec());
+        playing_cards.extend(cards);
+    }
+    playing_cards.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+
+    let jokers =
+        area_cards(&areas, "jokers")?.iter().map(|t| parse_joker(t, &game)).try_collect()?;
+
+    let mut data = RunData {
+        stake: Stake::from_i64(game.get::<i64>("stake")? - 1).ok_or_else(|| unknown("stake"))?,
+        rng: parse_rng(&game.get("pseudorandom")?)?,
+        shop: parse_shop(&game, &areas, &round_resets, &current_round)?,
+        cards: playing_cards.iter().map(|(_, card)| card.clone()).collect(),
+        deck_type: lookup(&save.get::<Table>("BACK")?.get::<String>("key")?, DeckType::game_name)?,
+        joker_slots: area_limit(&areas, "jokers")?,
+        consumables: area_cards(&areas, "consumeables")?
+            .iter()
+            .map(|card| lookup_consumable(&center(card)?))
+            .try_collect()?,
+        consumable_extra_value: area_cards(&areas, "consumeables")?
+            .iter()
+            .map(|card| card.get::<Table>("ability")?.get::<Option<u32>>("extra_value"))
+            .map_ok(Option::unwrap_or_default)
+            .try_collect()?,
+        consumable_slots: area_limit(&areas, "consumeables")?,
+        vouchers: [false; Voucher::COUNT],
+        starting_hands: round_resets.get("hands")?,
+        starting_discards: round_resets.get("discards")?,
+        money: game.get("dollars")?,
+        hand_size: area_limit(&areas, "hand")? as u32,
+        ante: round_resets.get("ante")?,
+        times_played: [0; HandType::COUNT],
+        base_chips: [0; HandType::COUNT],
+        base_mult: [0; HandType::COUNT],
+        hand_levels: [1; HandType::COUNT],
+        planet_unlocked: [false; HandType::COUNT],
+        times_boss_used: [0; BossBlindType::COUNT],
+        this_antes_boss: lookup(
+            &round_resets.get::<Table>("blind_choices")?.get::<String>("Boss")?,
+            BossBlindType::game_name,
+        )?,
+        boss_rerolled: round_resets.get::<Option<bool>>("boss_rerolled")?.unwrap_or(false),
+        blind_on_deck: BlindType::Small,
+        showman: false,
+        tags: save.get::<Option<Table>>("tags")?.map_or(Ok(Vec::new()), |tags| {
+            tags.sequence_values::<Table>()
+                .map(|tag| lookup(&tag?.get::<String>("key")?, Tag::game_name))
+                .try_collect()
+        })?,
+        ante_scaling: game
+            .get::<Table>("starting_params")?
+            .get::<Option<f64>>("ante_scaling")?
+            .unwrap_or(1.),
+        money_per_hand: 1.,
+        money_per_discard: 0.,
+        interest_cap: game.get("interest_cap")?,
+        no_interest: false,
+    };
+
+    let hands: Table = game.get("hands")?;
+    for hand_type in HandType::iter() {
+        let hand: Table = hands.get(hand_type.game_name())?;
+        let idx = hand_type as usize;
+
+        data.hand_levels[idx] = hand.get("level")?;
+        data.base_chips[idx] = hand.get("chips")?;
+        data.base_mult[idx] = hand.get("mult")?;
+        data.times_played[idx] = hand.get("played")?;
+        data.planet_unlocked[idx] = hand.get("visible")?;
+    }
+
+    for pair in game.get::<Table>("used_vouchers")?.pairs::<String, bool>() {
+        let (key, used) = pair?;
+        data.vouchers[lookup(&key, Voucher::game_name)? as usize] = used;
+    }
+
+    for pair in game.get::<Table>("bosses_used")?.pairs::<String, usize>() {
+        let (key, times) = pair?;
+        data.times_boss_used[lookup(&key, BossBlindType::game_name)? as usize] = times;
+    }
+
+    if let Some(modifiers) = game.get::<Option<Table>>("modifiers")? {
+        data.money_per_hand = modifiers.get::<Option<f64>>("money_per_hand")?.unwrap_or(1.);
+        data.money_per_discard = modifiers.get::<Option<f64>>("money_per_discard")?.unwrap_or(0.);
+        data.no_interest = modifiers.get::<Option<bool>>("no_interest")?.unwrap_or(false);
+    }
+
+    data.blind_on_deck = match game.get::<Option<String>>("blind_on_deck")?.as_deref() {
+        None | Some("Small") => BlindType::Small,
+        Some("Big") => BlindType::Big,
+        Some("Boss") => BlindType::Boss(data.this_antes_boss),
+        Some(other) => return Err(unknown(other)),
+    };
+
+    let mut run = Run { data, jokers, game_state: GameState::BlindSelection };
+    run.data.showman = run.has_joker(JokerType::Showman);
+    run.game_state = match save.get::<i64>("STATE")? {
+        SHOP => GameState::Shop,
+        BLIND_SELECT => GameState::BlindSelection,
+        SELECTING_HAND => {
+            let sort_ids = playing_cards.iter().map(|(sort_id, _)| *sort_id).collect_vec();
+            let index_of = |ids: &[f64]| {
+                ids.iter()
+                    .map(|id| sort_ids.iter().position(|other| other == id).unwrap())
+                    .collect()
+            };
+
+            GameState::Blind(Blind {
+                chips: 0.,
+                mult: 1.,
+                blind_type: run.data.blind_on_deck,
+                blind_data: run.data.blind_on_deck.default_data(),
+                cards: index_of(&area_sort_ids[0]),
+                selected: Hand::default(),
+                held: index_of(&area_sort_ids[1]),
+                face_down: index_of(&face_down),
+                score: game.get("chips")?,
+                requirement: save.get::<Table>("BLIND")?.get("chips")?,
+                hands: current_round.get("hands_left")?,
+                discards: current_round.get("discards_left")?,
+            })
+        }
+        ROUND_EVAL => GameState::CashOut {
+            earnings: current_round.get::<Option<f64>>("dollars")?.unwrap_or(0.),
+        },
+        state => return Err(JkrError::UnsupportedState(state)),
+    };
+
+    Ok(run)
+}
+
+fn parse_rng(pseudorandom: &Table) -> Result<BalatroRng> {
+    let mut rng = BalatroRng::new(pseudorandom.get("seed")?);
+
+    for pair in pseudorandom.pairs::<String, Value>() {
+        match pair? {
+            (key, Value::Number(state)) => rng.pseudorandom_state.insert(key, state),
+            (key, Value::Integer(state)) => rng.pseudorandom_state.insert(key, state as f64),
+            _ => None,
+        };
+    }
+
+    rng.pseudorandom_state.remove("hashed_seed");
+    Ok(rng)
+}
+
+fn parse_shop(game: &Table, areas: &Table, resets: &Table, round: &Table) -> Result<Shop> {
+    let mut shop = Shop {
+        weights: [
+            game.get("joker_rate")?,
+            game.get("tarot_rate")?,
+            game.get("planet_rate")?,
+            game.get("playing_card_rate")?,
+            game.get("spectral_rate")?,
+        ],
+        price_multiplier: 1. - game.get::<f64>("discount_percent")? / 100.,
+        base_reroll_price: resets.get("reroll_cost")?,
+        temp_base_reroll_price: resets.get("temp_reroll_cost")?,
+        reroll_price_increase: round.get::<Option<f64>>("reroll_cost_increase")?.unwrap_or(0.),
+        reroll_price: round.get("reroll_cost")?,
+        free_rerolls: round.get::<Option<u32>>("free_rerolls")?.unwrap_or(0),
+        size: game.get::<Table>("shop")?.get("joker_max")?,
+        edition_rate: game.get("edition_rate")?,
+        first_shop_buffoon: game.get::<Option<bool>>("first_shop_buffoon")?.unwrap_or(false),
+        ..Default::default()
+    };
+
+    shop.inventory = area_cards(areas, "shop_jokers")?
+        .iter()
+        .map(|card| parse_shop_item(card, game))
+        .try_collect()?;
+
+    shop.vouchers = area_cards(areas, "shop_vouchers")?
+        .iter()
+        .map(|card| lookup(&center(card)?, Voucher::game_name))
+        .try_collect()?;
+
+    for (slot, card) in shop.packs.iter_mut().zip(area_cards(areas, "shop_booster")?) {
+        *slot = Some(lookup_pack(&center(&card)?)?);
+    }
+
+    Ok(shop)
+}
+
+fn area_cards(areas: &Table, area: &str) -> Result<Vec<Table>> {
+    let Some(area) = areas.get::<Option<Table>>(area)? else {
+        return Ok(Vec::new());
+    };
+
+    Ok(area.get::<Table>("cards")?.sequence_values().try_collect()?)
+}
+
+fn area_limit(areas: &Table, area: &str) -> Result<usize> {
+    Ok(areas.get::<Table>(area)?.get::<Table>("config")?.get("card_limit")?)
+}
+
+fn center(card: &Table) -> Result<String> {
+    Ok(card.get::<Table>("save_fields")?.get("center")?)
+}
+
+fn lookup_consumable(key: &str) -> Result<Consumable> {
+    Tarot::iter()
+        .map(Consumable::TarotCard)
+        .chain(HandType::iter().map(Consumable::PlanetCard))
+        .chain(Spectral::iter().map(Consumable::SpectralCard))
+        .find(|consumable| consumable.game_name() == key)
+        .ok_or_else(|| unknown(key))
+}
+
+/// Packs have several art variants, `p_arcana_normal_1` to `p_arcana_normal_4`.
+fn lookup_pack(key: &str) -> Result<BoosterPackType> {
+    let art = |key: &'static str| key.trim_end_matches(|c: char| c.is_ascii_digit());
+    let key_art = key.trim_end_matches(|c: char| c.is_ascii_digit());
+
+    BoosterPackType::iter()
+        .find(|pack| art(pack.game_name()) == key_art)
+        .ok_or_else(|| unknown(key))
+}
+
+fn parse_shop_item(card: &Table, game: &Table) -> Result<ShopItem> {
+    let center = center(card)?;
+
+    if card.get::<Table>("save_fields")?.contains_key("card")? {
+        Ok(ShopItem::PlayingCard(parse_card(card)?.1))
+    } else if center.starts_with("j_") {
+        Ok(ShopItem::Joker(parse_joker(card, game)?))
+    } else {
+        Ok(ShopItem::Consumable(lookup_consumable(&center)?))
+    }
+}
+
+fn parse_edition(card: &Table) -> Result<JokerEdition> {
+    let Some(edition) = card.get::<Option<Table>>("edition")? else {
+        return Ok(JokerEdition::Base);
+    };
+
+    let flag = |key: &str| edition.get::<Option<bool>>(key).map(|set| set.unwrap_or(false));
+    Ok(match () {
+        _ if flag("foil")? => JokerEdition::Foil,
+        _ if flag("holo")? => JokerEdition::Holographic,
+        _ if flag("polychrome")? => JokerEdition::Polychrome,
+        _ if flag("negative")? => JokerEdition::Negative,
+        _ => JokerEdition::Base,
+    })
+}
+
+/// Returns the card with its `sort_id`, which orders `G.playing_cards`.
+fn parse_card(card: &Table) -> Result<(f64, Card)> {
+    let key: String = card.get::<Table>("save_fields")?.get("card")?;
+    let (suit, rank) = key.split_once('_').ok_or_else(|| unknown(&key))?;
+
+    let suit = match suit {
+        "S" => Suit::Spade,
+        "H" => Suit::Heart,
+        "C" => Suit::Club,
+        "D" => Suit::Diamond,
+        _ => return Err(unknown(&key)),
+    };
+
+    let rank = match rank {
+        "T" => Rank::Rank10,
+        "J" => Rank::Jack,
+        "Q" => Rank::Queen,
+        "K" => Rank::King,
+        "A" => Rank::Ace,
+        number => match number.parse::<usize>() {
+            Ok(n @ 2..=9) => Rank::iter().nth(n - 2).unwrap(),
+            _ => return Err(unknown(&key)),
+        },
+    };
+
+    let mut parsed = Card::new(suit, rank);
+    parsed.enhancement = lookup(&center(card)?, Enhancement::game_name)?;
+    parsed.edition = match parse_edition(card)? {
+        JokerEdition::Base => Edition::Base,
+        JokerEdition::Foil => Edition::Foil,
+        JokerEdition::Holographic => Edition::Holographic,
+        JokerEdition::Polychrome => Edition::Polychrome,
+        JokerEdition::Negative => return Err(unknown("negative playing card")),
+    };
+    parsed.seal = match card.get::<Option<String>>("seal")?.as_deref() {
+        None => Seal::None,
+        Some("Gold") => Seal::Gold,
+        Some("Red") => Seal::Red,
+        Some("Blue") => Seal::Blue,
+        Some("Purple") => Seal::Purple,
+        Some(other) => return Err(unknown(other)),
+    };
+
+    let ability: Table = card.get("ability")?;
+    parsed.chips += ability.get::<Option<u32>>("perma_bonus")?.unwrap_or(0);
+
+    Ok((card.get("sort_id")?, parsed))
+}
+
+fn parse_joker(card: &Table, game: &Table) -> Result<Joker> {
+    let joker_type = lookup(&center(card)?, JokerType::game_name)?;
+    let ability: Table = card.get("ability")?;
+    let sticker = |key: &str| ability.get::<Option<bool>>(key).map(|set| set.unwrap_or(false));
+
+    let data = match joker_type {
+        JokerType::CeremonialDagger => {
+            JokerInternalState::CeremonialDagger { mult: ability.get::<f64>("mult")? as u32 }
+        }
+        JokerType::TurtleBean => JokerInternalState::TurtleBean {
+            hand_size: ability.get::<Table>("extra")?.get("h_size")?,
+        },
+        JokerType::CardSharp => {
+            let hands: Table = game.get("hands")?;
+            let mut played_before = [false; HandType::COUNT];
+
+            for hand_type in HandType::iter() {
+                let hand: Table = hands.get(hand_type.game_name())?;
+                played_before[hand_type as usize] = hand.get::<u32>("played_this_round")? > 0;
+            }
+
+            JokerInternalState::CardSharp { played_before }
+        }
+        _ => JokerInternalState::None,
+    };
+
+    Ok(Joker {
+        data,
+        joker_type,
+        edition: parse_edition(card)?,
+        stickers: Stickers {
+            eternal: sticker("eternal")?,
+            perishable: sticker("perishable")?,
+            rental: sticker("rental")?,
+        },
+        sell_value: card.get::<Option<f64>>("sell_cost")?.unwrap_or(0.) as u32,
+        debuffed: card.get::<Option<bool>>("debuff")?.unwrap_or(false),
+        dispatcher_order: DispatcherOrder::default(),
+    })
+}
+
+fn lua_str(text: &str) -> String {
+    let escaped = text
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+        .replace('\0', "\\0");
+    format!("\"{escaped}\"")
+}
+
+fn lua_num(number: f64) -> String {
+    match number {
+        _ if number.is_nan() => "0/0".to_string(),
+        _ if number.is_infinite() => format!("{}1/0", if number < 0. { "-" } else { "" }),
+        _ if number.fract() == 0. && number.abs() < 1e15 => format!("{}", number as i64),
+        _ => format!("{number:?}"),
+    }
+}
+
+/// Tables in the patch that replace the save's instead of being merged into it.
+const REPLACED: [&str; 6] =
+    ["cards", "tags", "pseudorandom", "used_vouchers", "bosses_used", "edition"];
+
+fn merge(save: &Table, patch: &Table) -> Result<()> {
+    for pair in patch.pairs::<Value, Value>() {
+        let (key, value) = pair?;
+        let replaced =
+            matches!(&key, Value::String(key) if REPLACED.contains(&&*key.to_string_lossy()));
+
+        match (value, save.raw_get::<Value>(key.clone())?) {
+            (Value::Table(patch), Value::Table(save)) if !replaced => merge(&save, &patch)?,
+            (value, _) => save.raw_set(key, value)?,
+        }
+    }
+
+    Ok(())
+}
+
+/// The game's `STR_PACK`, with the keys sorted so the same run always packs the same way.
+fn pack(table: &Table, text: &mut String) -> Result<()> {
+    let order = |key: &Value| match key {
+        Value::Integer(i) => (0, *i as f64, String::new()),
+        Value::Number(n) => (0, *n, String::new()),
+        key => (1, 0., pack_scalar(key)),
+    };
+
+    let mut pairs: Vec<(Value, Value)> = table.pairs().try_collect()?;
+    pairs.sort_by(|(a, _), (b, _)| order(a).partial_cmp(&order(b)).unwrap_or(Ordering::Equal));
+
+    text.push('{');
+    for (key, value) in pairs {
+        let _ = write!(text, "[{}]=", pack_scalar(&key));
+        match value {
+            Value::Table(table) => pack(&table, text)?,
+            value => text.push_str(&pack_scalar(&value)),
+        }
+        text.push(',');
+    }
+    text.push('}');
+
+    Ok(())
+}
+
+/// Saves only hold plain data, anything else is dropped like the game's `recursive_table_cull`.
+fn pack_scalar(value: &Value) -> String {
+    match value {
+        Value::Boolean(set) => set.to_string(),
+        Value::Integer(i) => i.to_string(),
+        Value::Number(n) => lua_num(*n),
+        Value::String(text) => lua_str(&text.to_string_lossy()),
+        _ => "nil".to_string(),
+    }
+}
+
+/// The display name the game's effects compare against, `RideTheBus` is "Ride the Bus".
+fn game_label(value: impl Debug) -> String {
+    let name = format!("{value:?}");
+    let label = match name.as_str() {
+        "EightBall" => "8 Ball",
+        "OopsAll6s" => "Oops! All 6s",
+        "Seance" => "Séance",
+        "RiffRaff" => "Riff-raff",
+        "MailInRebate" => "Mail-In Rebate",
+        "MrBones" => "Mr. Bones",
+        "DriversLicense" => "Driver's License",
+        "DirectorsCut" => "Director's Cut",
+        "Canio" => "Caino",
+        _ => "",
+    };
+    if !label.is_empty() {
+        return label.to_string();
+    }
+
+    let mut words = vec![String::new()];
+    for (prev, c) in std::iter::once(' ').chain(name.chars()).tuple_windows() {
+        if (c.is_ascii_uppercase() && prev.is_ascii_lowercase())
+            || (c.is_ascii_digit() && prev.is_ascii_alphabetic())
+        {
+            words.push(String::new());
+        }
+        words.last_mut().unwrap().push(c);
+    }
+
+    words
+        .iter()
+        .enumerate()
+        .map(|(i, word)| match word.as_str() {
+            "The" | "Of" | "And" if i > 0 => word.to_lowercase(),
+            _ => word.clone(),
+        })
+        .join(" ")
+}
+
+/// Builds a card's `ability` table the way `Card:set_ability` does from its center's config.
+const ABILITY: &str = r#"
+local function ability(name, set, effect, config, fields)
+    local ability = {
+        name = name, set = set, effect = effect,
+        mult = config.mult or 0, h_mult = config.h_mult or 0, h_x_mult = config.h_x_mult or 0,
+        h_dollars = config.h_dollars or 0, p_dollars = config.p_dollars or 0,
+        t_mult = config.t_mult or 0, t_chips = config.t_chips or 0, x_mult = config.Xmult or 1,
+        h_size = config.h_size or 0, d_size = config.d_size or 0, extra = config.extra,
+        extra_value = 0, type = config.type or "", perma_bonus = 0, bonus = config.bonus or 0,
+        hands_played_at_create = 0,
+    }
+    if set == "Tarot" or set == "Planet" or set == "Spectral" then
+        ability.consumeable = config
+    end
+    for key, value in pairs(fields) do
+        ability[key] = value
+    end
+    return ability
+end
+"#;
+
+fn write_run(run: &Run) -> String {
+    let data = &run.data;
+    let shop = &data.shop;
+
+    let (state, blind, earnings) = match &run.game_state {
+        GameState::Shop => (SHOP, None, 0.),
+        GameState::BlindSelection => (BLIND_SELECT, None, 0.),
+        GameState::Blind(blind) => (SELECTING_HAND, Some(blind), 0.),
+        GameState::CashOut { earnings } => (ROUND_EVAL, None, *earnings),
+    };
+
+    let (deck, hand, face_down) = match blind {
+        Some(blind) => (blind.cards.clone(), blind.held.clone(), blind.face_down.as_slice()),
+        None => ((0..data.cards.len()).collect(), Vec::new(), &[][..]),
+    };
+    let discard = (0..data.cards.len()).filter(|i| !deck.contains(i) && !hand.contains(i));
+    let playing_cards = |idxs: &[usize]| {
+        idxs.iter()
+            .map(|&i| {
+                let card = &data.cards[i];
+                let base_cost = 1. + card.edition.extra_cost();
+                let costs = [
+                    1.,
+                    card.edition.extra_cost(),
+                    shop.cost(base_cost),
+                    shop.sell_value(base_cost),
+                ];
+                write_card(card, i + 1, Some(i + 1), costs, face_down.contains(&i))
+            })
+            .join(",")
+    };
+
+    let last_sort_id = Cell::new(data.cards.len());
+    let sort_id = || {
+        last_sort_id.set(last_sort_id.get() + 1);
+        last_sort_id.get()
+    };
+    let center_costs =
+        |base_cost: f64, cost: f64| [base_cost, 0., cost, shop.sell_value(base_cost)];
+
+    let played_before = run.jokers.iter().find_map(|joker| match joker.data {
+        JokerInternalState::CardSharp { played_before } => Some(played_before),
+        _ => None,
+    });
+    let hands = HandType::iter()
+        .map(|hand_type| {
+            let idx = hand_type as usize;
+            format!(
+                "[{}]={{level={},chips={},mult={},played={},played_this_round={},visible={}}}",
+                lua_str(hand_type.game_name()),
+                data.hand_levels[idx],
+                data.base_chips[idx],
+                data.base_mult[idx],
+                data.times_played[idx],
+                played_before.is_some_and(|played| played[idx]) as u32,
+                data.planet_unlocked[idx],
+            )
+        })
+        .join(",");
+
+    let pseudorandom = data
+        .rng
+        .pseudorandom_state
+        .iter()
+        .sorted_by(|(a, _), (b, _)| a.cmp(b))
+        .map(|(key, state)| format!("[{}]={}", lua_str(key), lua_num(*state)))
+        .chain([
+            format!("seed={}", lua_str(&data.rng.seed)),
+            format!("hashed_seed={}", lua_num(data.rng.hashed_seed)),
+        ])
+        .join(",");
+
+    let used_vouchers = Voucher::iter()
+        .filter(|&voucher| data.vouchers[voucher as usize])
+        .map(|voucher| format!("{}=true", voucher.game_name()))
+        .join(",");
+    let bosses_used = BossBlindType::iter()
+        .map(|boss| format!("{}={}", boss.game_name(), data.times_boss_used[boss as usize]))
+        .join(",");
+    let (blind_on_deck, on_deck) = match data.blind_on_deck {
+        BlindType::Small => ("Small", 0),
+        BlindType::Big => ("Big", 1),
+        BlindType::Boss(_) => ("Boss", 2),
+    };
+    let blind_states = ["Small", "Big", "Boss"]
+        .iter()
+        .enumerate()
+        .map(|(i, choice)| {
+            let state = match i.cmp(&on_deck) {
+                Ordering::Less => "Defeated",
+                Ordering::Equal if blind.is_some() => "Current",
+                Ordering::Equal => "Select",
+                Ordering::Greater => "Upcoming",
+            };
+            format!("{choice}={}", lua_str(state))
+        })
+        .join(",");
+
+    let oops = run.jokers.iter().filter(|joker| joker.joker_type == JokerType::OopsAll6s);
+    let tags = data
+        .tags
+        .iter()
+        .enumerate()
+        .map(|(i, tag)| {
+            format!("{{key={},tally={},ability={{}}}}", lua_str(tag.game_name()), i + 1)
+        })
+        .join(",");
+
+    let area = |limit: usize, cards: String| {
+        format!("{{config={{card_limit={limit},temp_limit={limit}}},cards={{{cards}}}}}")
+    };
+
+    let mut text = format!("{ABILITY}return {{");
+    let _ = write!(
+        text,
+        "STATE={state},BACK={{key={},name={}}},BLIND={},tags={{{tags}}},",
+        lua_str(data.deck_type.game_name()),
+        lua_str(&format!("{} Deck", game_label(data.deck_type))),
+        write_blind(data, blind),
+    );
+
+    let _ = write!(
+        text,
+        "GAME={{stake={},dollars={},interest_cap={},chips={},edition_rate={},discount_percent={},\
+         joker_rate={},tarot_rate={},planet_rate={},playing_card_rate={},spectral_rate={},\
+         first_shop_buffoon={},shop={{joker_max={}}},starting_params={{ante_scaling={}}},\
+         probabilities={{normal={}}},modifiers={{money_per_hand={},money_per_discard={},\
+         no_interest={}}},used_vouchers={{{used_vouchers}}},bosses_used={{{bosses_used}}},\
+         blind_on_deck={},tags={{{}}},round_resets={{ante={},blind_ante={},hands={},discards={},\
+         reroll_cost={},boss_rerolled={},blind_states={{{blind_states}}},\
+         blind_choices={{Small=\"bl_small\",Big=\"bl_big\",Boss={}}}}},\
+         current_round={{reroll_cost={},reroll_cost_increase={},free_rerolls={},hands_left={},\
+         discards_left={},dollars={}}},pseudorandom={{{pseudorandom}}},hands={{{hands}}}}},",
+        data.stake as usize + 1,
+        lua_num(data.money),
+        lua_num(data.interest_cap),
+        lua_num(blind.map_or(0., |blind| blind.score)),
+        lua_num(shop.edition_rate),
+        lua_num(((1. - shop.price_multiplier) * 100.).round()),
+        lua_num(shop.weights[0]),
+        lua_num(shop.weights[1]),
+        lua_num(shop.weights[2]),
+        lua_num(shop.weights[3]),
+        lua_num(shop.weights[4]),
+        shop.first_shop_buffoon,
+        shop.size,
+        lua_num(data.ante_scaling),
+        1 << oops.count(),
+        lua_num(data.money_per_hand),
+        lua_num(data.money_per_discard),
+        data.no_interest,
+        lua_str(blind_on_deck),
+        data.tags.iter().map(|_| lua_str("\"MANUAL_REPLACE\"")).join(","),
+        data.ante,
+        data.ante,
+        data.starting_hands,
+        data.starting_discards,
+        lua_num(shop.base_reroll_price),
+        data.boss_rerolled,
+        lua_str(data.this_antes_boss.game_name()),
+        lua_num(shop.reroll_price),
+        lua_num(shop.reroll_price_increase),
+        shop.free_rerolls,
+        blind.map_or(data.starting_hands, |blind| blind.hands),
+        blind.map_or(data.starting_discards, |blind| blind.discards),
+        lua_num(earnings),
+    );
+
+    let mut jokers = run.jokers.iter().map(|joker| {
+        let costs = [
+            joker.joker_type.base_cost(),
+            joker.edition.extra_cost(),
+            shop.cost(joker.base_cost()),
+            joker.sell_value as f64,
+        ];
+        write_joker(joker, sort_id(), costs, true)
+    });
+    let mut consumables = data.consumables.iter().enumerate().map(|(idx, consumable)| {
+        let base_cost = consumable.base_cost();
+        let costs = [base_cost, 0., shop.cost(base_cost), data.consumable_sell_value(idx)];
+        let extra_value = data.consumable_extra_value.get(idx).copied().unwrap_or(0);
+        write_consumable(consumable, sort_id(), costs, Some(extra_value))
+    });
+    let mut shop_items = shop.inventory.iter().map(|item| {
+        let cost = run.shop_item_cost(item);
+        match item {
+            ShopItem::Consumable(consumable) => {
+                let costs = center_costs(consumable.base_cost(), cost);
+                write_consumable(consumable, sort_id(), costs, None)
+            }
+            ShopItem::Joker(joker) => {
+                let costs = [
+                    joker.joker_type.base_cost(),
+                    joker.edition.extra_cost(),
+                    cost,
+                    shop.sell_value(joker.base_cost()),
+                ];
+                write_joker(joker, sort_id(), costs, false)
+            }
+            ShopItem::PlayingCard(card) => {
+                let costs =
+                    [1., card.edition.extra_cost(), cost, shop.sell_value(item.base_cost())];
+                write_card(card, sort_id(), None, costs, false)
+            }
+        }
+    });
+    let mut vouchers = shop.vouchers.iter().map(|voucher| {
+        let costs = center_costs(voucher.base_cost(), shop.cost(voucher.base_cost()));
+        write_center(
+            sort_id(),
+            voucher.game_name(),
+            "Voucher",
+            &game_label(voucher),
+            voucher_config(*voucher),
+            costs,
+            false,
+        )
+    });
+    let mut packs = shop.packs.iter().flatten().map(|&pack| {
+        let name = match pack.size() {
+            BoosterPackSize::Normal => format!("{} Pack", pack.kind()),
+            size => format!("{size} {} Pack", pack.kind()),
+        };
+        let config = format!("extra={},choose={}", pack.card_count(), pack.picks());
+        let costs = center_costs(pack.base_cost(), run.booster_pack_cost(pack));
+        write_center(sort_id(), pack.game_name(), "Booster", &name, &config, costs, false)
+    });
+
+    let _ = write!(
+        text,
+        "cardAreas={{deck={},hand={},discard={},jokers={},consumeables={},shop_jokers={},\
+         shop_vouchers={},shop_booster={}}}}}",
+        area(data.cards.len(), playing_cards(&deck)),
+        area(data.hand_size as usize, playing_cards(&hand)),
+        area(data.cards.len(), playing_cards(&discard.collect_vec())),
+        area(data.joker_slots, jokers.join(",")),
+        area(data.consumable_slots, consumables.join(",")),
+        area(shop.size, shop_items.join(",")),
+        area(shop.vouchers.len(), vouchers.join(",")),
+        area(2, packs.join(",")),
+    );
+
+    text
+}
+
+fn write_blind(data: &RunData, blind: Option<&Blind>) -> String {
+    let Some(blind) = blind else {
+        return "{name=\"\",config_blind=\"\",boss=false,mult=0,dollars=0,chips=0,chip_text=\"0\",\
+                disabled=false}"
+            .to_string();
+    };
+
+    let (name, key, mult) = match blind.blind_type {
+        BlindType::Small => ("Small Blind".to_string(), "bl_small", 1.),
+        BlindType::Big => ("Big Blind".to_string(), "bl_big", 1.5),
+        BlindType::Boss(boss) => {
+            let mult = match boss {
+                BossBlindType::TheWall => 4.,
+                BossBlindType::VioletVessel => 6.,
+                BossBlindType::TheNeedle => 1.,
+                _ => 2.,
+            };
+            (game_label(boss), boss.game_name(), mult)
+        }
+    };
+
+    format!(
+        "{{name={},config_blind={},boss={},mult={},dollars={},chips={},chip_text={},disabled=false}}",
+        lua_str(&name),
+        lua_str(key),
+        matches!(blind.blind_type, BlindType::Boss(_)),
+        lua_num(mult),
+        lua_num(blind.blind_type.reward(data.stake)),
+        lua_num(blind.requirement),
+        lua_str(&lua_num(blind.requirement)),
+    )
+}
+
+/// `Card:save()` for cards without a rank or suit.
+const EMPTY_BASE: &str = "{nominal=0,suit_nominal=0,face_nominal=0,times_played=0}";
+
+/// The fields every `Card:save()` has, `costs` is `[base_cost, extra_cost, cost, sell_cost]`.
+fn write_saved(
+    sort_id: usize,
+    save_fields: String,
+    ability: String,
+    costs: [f64; 4],
+    fields: String,
+    face_down: bool,
+) -> String {
+    let [base_cost, extra_cost, cost, sell_cost] = costs.map(lua_num);
+    let facing = if face_down { "back" } else { "front" };
+
+    format!(
+        "{{sort_id={sort_id},save_fields={{{save_fields}}},ability={ability},base_cost={base_cost},\
+         extra_cost={extra_cost},cost={cost},sell_cost={sell_cost},facing=\"{facing}\",\
+         sprite_facing=\"{facing}\",highlighted=false,{fields}}}"
+    )
+}
+
+fn write_center(
+    sort_id: usize,
+    center: &str,
+    set: &str,
+    name: &str,
+    config: &str,
+    costs: [f64; 4],
+    owned: bool,
+) -> String {
+    write_saved(
+        sort_id,
+        format!("center={}", lua_str(center)),
+        format!("ability({},{},\"\",{{{config}}},{{}})", lua_str(name), lua_str(set)),
+        costs,
+        format!(
+            "label={},base={EMPTY_BASE},params={{}},added_to_deck={owned},debuff=false,",
+            lua_str(name)
+        ),
+        false,
+    )
+}
+
+/// Consumables still for sale have no `extra_value`.
+fn write_consumable(
+    consumable: &Consumable,
+    sort_id: usize,
+    costs: [f64; 4],
+    extra_value: Option<u32>,
+) -> String {
+    let (set, name) = match consumable {
+        Consumable::TarotCard(tarot) => ("Tarot", game_label(tarot)),
+        Consumable::PlanetCard(planet) => ("Planet", planet.as_string().to_string()),
+        Consumable::SpectralCard(spectral) => ("Spectral", game_label(spectral)),
+    };
+
+    write_saved(
+        sort_id,
+        format!("center={}", lua_str(consumable.game_name())),
+        format!(
+            "ability({},{},\"\",{{{}}},{{extra_value={}}})",
+            lua_str(&name),
+            lua_str(set),
+            consumable_config(consumable),
+            extra_value.unwrap_or(0),
+        ),
+        costs,
+        format!(
+            "label={},base={EMPTY_BASE},params={{}},added_to_deck={},debuff=false,",
+            lua_str(&name),
+            extra_value.is_some(),
+        ),
+        false,
+    )
+}
+
+fn write_edition(edition: JokerEdition) -> String {
+    let (key, bonus) = match edition {
+        JokerEdition::Base => return String::new(),
+        JokerEdition::Foil => ("foil", "chips=50,"),
+        JokerEdition::Holographic => ("holo", "mult=10,"),
+        JokerEdition::Polychrome => ("polychrome", "x_mult=1.5,"),
+        JokerEdition::Negative => ("negative", ""),
+    };
+
+    format!("edition={{{key}=true,{bonus}type={}}},", lua_str(key))
+}
+
+/// `playing_card` is the card's place in `G.playing_cards`, cards still for sale have none.
+fn write_card(
+    card: &Card,
+    sort_id: usize,
+    playing_card: Option<usize>,
+    costs: [f64; 4],
+    face_down: bool,
+) -> String {
+    let (suit, suit_name, suit_nominal) = match card.suit {
+        Suit::Spade => ("S", "Spades", 0.04),
+        Suit::Heart => ("H", "Hearts", 0.03),
+        Suit::Club => ("C", "Clubs", 0.02),
+        Suit::Diamond => ("D", "Diamonds", 0.01),
+    };
+
+    let number = (card.rank as usize + 2).to_string();
+    let (rank, value, nominal, face_nominal) = match card.rank {
+        Rank::Rank10 => ("T", "10", 10, 0.),
+        Rank::Jack => ("J", "Jack", 10, 0.1),
+        Rank::Queen => ("Q", "Queen", 10, 0.2),
+        Rank::King => ("K", "King", 10, 0.3),
+        Rank::Ace => ("A", "Ace", 11, 0.4),
+        rank => (number.as_str(), number.as_str(), rank as usize + 2, 0.),
+    };
+
+    let (name, effect, config) = match card.enhancement {
+        Enhancement::None => ("Default Base", "Base", ""),
+        Enhancement::BonusCard => ("Bonus", "Bonus Card", "bonus=30"),
+        Enhancement::MultCard => ("Mult", "Mult Card", "mult=4"),
+        Enhancement::WildCard => ("Wild Card", "Wild Card", ""),
+        Enhancement::GlassCard => ("Glass Card", "Glass Card", "Xmult=2,extra=4"),
+        Enhancement::SteelCard => ("Steel Card", "Steel Card", "h_x_mult=1.5"),
+        Enhancement::StoneCard => ("Stone Card", "Stone Card", "bonus=50"),
+        Enhancement::GoldCard => ("Gold Card", "Gold Card", "h_dollars=3"),
+        Enhancement::LuckyCard => ("Lucky Card", "Lucky Card", "mult=20,p_dollars=20"),
+    };
+    let (set, label) = match card.enhancement {
+        Enhancement::None => ("Default", "Base Card"),
+        _ => ("Enhanced", effect),
+    };
+
+    let edition = write_edition(match card.edition {
+        Edition::Base => JokerEdition::Base,
+        Edition::Foil => JokerEdition::Foil,
+        Edition::Holographic => JokerEdition::Holographic,
+        Edition::Polychrome => JokerEdition::Polychrome,
+    });
+
+    let seal = match card.seal {
+        Seal::None => String::new(),
+        Seal::Gold => "seal=\"Gold\",".to_string(),
+        Seal::Red => "seal=\"Red\",".to_string(),
+        Seal::Blue => "seal=\"Blue\",".to_string(),
+        Seal::Purple => "seal=\"Purple\",".to_string(),
+    };
+
+    let params = match playing_card {
+        Some(idx) => format!("params={{playing_card={idx},viewed_back=true}},playing_card={idx},"),
+        None => "params={},".to_string(),
+    };
+
+    let perma_bonus = card.chips.saturating_sub(Card::new(card.suit, card.rank).chips);
+    let base = format!(
+        "{{name={},suit={},value={},original_value={},id={},nominal={nominal},suit_nominal={},\
+         suit_nominal_original={},face_nominal={},times_played=0}}",
+        lua_str(&format!("{value} of {suit_name}")),
+        lua_str(suit_name),
+        lua_str(value),
+        lua_str(value),
+        card.rank as usize + 2,
+        lua_num(suit_nominal),
+        lua_num(suit_nominal / 10.),
+        lua_num(face_nominal),
+    );
+
+    write_saved(
+        sort_id,
+        format!("center={},card=\"{suit}_{rank}\"", lua_str(card.enhancement.game_name())),
+        format!(
+            "ability({},{},{},{{{config}}},{{perma_bonus={perma_bonus}}})",
+            lua_str(name),
+            lua_str(set),
+            lua_str(effect),
+        ),
+        costs,
+        format!(
+            "label={},base={base},{params}added_to_deck={},debuff=false,{edition}{seal}",
+            lua_str(label),
+            playing_card.is_some(),
+        ),
+        face_down,
+    )
+}
+
+fn write_joker(joker: &Joker, sort_id: usize, costs: [f64; 4], owned: bool) -> String {
+    let mut fields = match joker.data {
+        JokerInternalState::CeremonialDagger { mult } => format!("mult={mult},"),
+        JokerInternalState::TurtleBean { hand_size } => {
+            format!("extra={{h_size={hand_size},h_mod=1}},")
+        }
+        _ => String::new(),
+    };
+
+    fields += match joker.joker_type {
+        JokerType::InvisibleJoker => "invis_rounds=0,",
+        JokerType::LoyaltyCard => "loyalty_remaining=5,",
+        JokerType::ToDoList => "to_do_poker_hand=\"High Card\",",
+        JokerType::Canio => "caino_xmult=1,",
+        JokerType::Yorick => "yorick_discards=23,",
+        _ => "",
+    };
+
+    if joker.stickers.perishable {
+        fields += "perish_tally=5,";
+    }
+
+    let name = game_label(joker.joker_type);
+    write_saved(
+        sort_id,
+        format!("center={}", lua_str(joker.joker_type.game_name())),
+        format!(
+            "ability({},\"Joker\",\"\",{{{}}},{{{fields}eternal={},perishable={},rental={}}})",
+            lua_str(&name),
+            joker_config(joker.joker_type),
+            joker.stickers.eternal,
+            joker.stickers.perishable,
+            joker.stickers.rental,
+        ),
+        costs,
+        format!(
+            "label={},base={EMPTY_BASE},params={{}},added_to_deck={owned},debuff={},{}",
+            lua_str(&name),
+            joker.debuffed,
+            write_edition(joker.edition),
+        ),
+        false,
+    )
+}
+
+/// The center `config` tables from the game's `P_CENTERS`.
+fn joker_config(joker_type: JokerType) -> &'static str {
+    match joker_type.game_name() {
+        "j_joker" => "mult=4",
+        "j_greedy_joker" => "extra={s_mult=3,suit=\"Diamonds\"}",
+        "j_lusty_joker" => "extra={s_mult=3,suit=\"Hearts\"}",
+        "j_wrathful_joker" => "extra={s_mult=3,suit=\"Spades\"}",
+        "j_gluttenous_joker" => "extra={s_mult=3,suit=\"Clubs\"}",
+        "j_jolly" => "t_mult=8,type=\"Pair\"",
+        "j_zany" => "t_mult=12,type=\"Three of a Kind\"",
+        "j_mad" => "t_mult=10,type=\"Two Pair\"",
+        "j_crazy" => "t_mult=12,type=\"Straight\"",
+        "j_droll" => "t_mult=10,type=\"Flush\"",
+        "j_sly" => "t_chips=50,type=\"Pair\"",
+        "j_wily" => "t_chips=100,type=\"Three of a Kind\"",
+        "j_clever" => "t_chips=80,type=\"Two Pair\"",
+        "j_devious" => "t_chips=100,type=\"Straight\"",
+        "j_crafty" => "t_chips=80,type=\"Flush\"",
+        "j_half" => "extra={mult=20,size=3}",
+        "j_mime" | "j_marble" | "j_dusk" | "j_chaos" | "j_hack" | "j_supernova"
+        | "j_ride_the_bus" | "j_cloud_9" | "j_gift" | "j_to_the_moon" | "j_fortune_teller"
+        | "j_sock_and_buskin" | "j_hanging_chad" | "j_rough_gem" | "j_satellite" | "j_caino" => {
+            "extra=1"
+        }
+        "j_credit_card" => "extra=20",
+        "j_ceremonial" => "mult=0",
+        "j_banner" => "extra=30",
+        "j_mystic_summit" => "extra={mult=15,d_remaining=0}",
+        "j_loyalty_card" => "extra={Xmult=4,every=5}",
+        "j_8_ball" | "j_space" | "j_even_steven" | "j_vagabond" | "j_erosion" | "j_golden"
+        | "j_ticket" => "extra=4",
+        "j_misprint" => "extra={max=23,min=0}",
+        "j_fibonacci" | "j_matador" => "extra=8",
+        "j_steel_joker" | "j_obelisk" => "extra=0.2,Xmult=1",
+        "j_scary_face" => "extra=30",
+        "j_abstract" | "j_egg" | "j_burglar" | "j_blackboard" | "j_red_card" | "j_trading"
+        | "j_acrobat" | "j_flower_pot" | "j_drivers_license" => "extra=3",
+        "j_delayed_grat" | "j_blue_joker" | "j_riff_raff" | "j_photograph" | "j_hallucination"
+        | "j_bull" | "j_trousers" | "j_idol" | "j_seeing_double" | "j_invisible"
+        | "j_triboulet" => "extra=2",
+        "j_gros_michel" => "extra={odds=6,mult=15}",
+        "j_odd_todd" => "extra=31",
+        "j_scholar" => "extra={mult=4,chips=20}",
+        "j_business" => "extra=2",
+        "j_runner" => "extra={chips=0,chip_mod=15}",
+        "j_ice_cream" => "extra={chips=100,chip_mod=5}",
+        "j_constellation" | "j_vampire" => "extra=0.1,Xmult=1",
+        "j_hiker" | "j_mail" | "j_smiley" => "extra=5",
+        "j_faceless" => "extra={dollars=5,faces=3}",
+        "j_green_joker" => "extra={hand_add=1,discard_sub=1}",
+        "j_todo_list" => "extra={dollars=4,poker_hand=\"High Card\"}",
+        "j_cavendish" => "extra={odds=1000,Xmult=3}",
+        "j_card_sharp" => "extra={Xmult=3}",
+        "j_madness" | "j_hit_the_road" => "extra=0.5,Xmult=1",
+        "j_square" => "extra={chips=0,chip_mod=4}",
+        "j_seance" => "extra={poker_hand=\"Straight Flush\"}",
+        "j_hologram" | "j_lucky_cat" | "j_throwback" | "j_campfire" => "extra=0.25,Xmult=1",
+        "j_baron" | "j_baseball" | "j_ancient" => "extra=1.5",
+        "j_rocket" => "extra={dollars=1,increase=2}",
+        "j_turtle_bean" => "extra={h_size=5,h_mod=1}",
+        "j_reserved_parking" => "extra={odds=2,dollars=1}",
+        "j_juggler" => "h_size=1",
+        "j_drunkard" => "d_size=1",
+        "j_stone" => "extra=25",
+        "j_flash" => "extra=2,mult=0",
+        "j_popcorn" => "mult=20,extra=4",
+        "j_ramen" => "Xmult=2,extra=0.01",
+        "j_walkie_talkie" => "extra={chips=10,mult=4}",
+        "j_selzer" => "extra=10",
+        "j_castle" => "extra={chips=0,chip_mod=3}",
+        "j_swashbuckler" => "mult=1",
+        "j_troubadour" => "extra={h_size=2,h_plays=-1}",
+        "j_bloodstone" => "extra={odds=2,Xmult=1.5}",
+        "j_arrowhead" => "extra=50",
+        "j_onyx_agate" => "extra=7",
+        "j_glass" => "extra=0.75,Xmult=1",
+        "j_wee" => "extra={chips=0,chip_mod=8}",
+        "j_merry_andy" => "d_size=3,h_size=-1",
+        "j_duo" => "Xmult=2,type=\"Pair\"",
+        "j_trio" => "Xmult=3,type=\"Three of a Kind\"",
+        "j_family" => "Xmult=4,type=\"Four of a Kind\"",
+        "j_order" => "Xmult=3,type=\"Straight\"",
+        "j_tribe" => "Xmult=2,type=\"Flush\"",
+        "j_stuntman" => "extra={h_size=2,chip_mod=250}",
+        "j_shoot_the_moon" => "extra=13",
+        "j_burnt" => "h_size=0,extra=4",
+        "j_bootstraps" => "extra={mult=2,dollars=5}",
+        "j_yorick" => "extra={xmult=1,discards=23}",
+        _ => "",
+    }
+}
+
+fn consumable_config(consumable: &Consumable) -> String {
+    let config = match consumable.game_name() {
+        "c_magician" => "mod_conv=\"m_lucky\",max_highlighted=2",
+        "c_high_priestess" => "planets=2",
+        "c_empress" => "mod_conv=\"m_mult\",max_highlighted=2",
+        "c_emperor" => "tarots=2",
+        "c_heirophant" => "mod_conv=\"m_bonus\",max_highlighted=2",
+        "c_lovers" => "mod_conv=\"m_wild\",max_highlighted=1",
+        "c_chariot" => "mod_conv=\"m_steel\",max_highlighted=1",
+        "c_justice" => "mod_conv=\"m_glass\",max_highlighted=1",
+        "c_hermit" => "extra=20",
+        "c_wheel_of_fortune" => "extra=4",
+        "c_strength" => "mod_conv=\"up_rank\",max_highlighted=2",
+        "c_hanged_man" => "remove_card=true,max_highlighted=2",
+        "c_death" => "mod_conv=\"card\",max_highlighted=2,min_highlighted=2",
+        "c_temperance" => "extra=50",
+        "c_devil" => "mod_conv=\"m_gold\",max_highlighted=1",
+        "c_tower" => "mod_conv=\"m_stone\",max_highlighted=1",
+        "c_star" => "suit_conv=\"Diamonds\",max_highlighted=3",
+        "c_moon" => "suit_conv=\"Clubs\",max_highlighted=3",
+        "c_sun" => "suit_conv=\"Hearts\",max_highlighted=3",
+        "c_world" => "suit_conv=\"Spades\",max_highlighted=3",
+        "c_familiar" => "remove_card=true,extra=3",
+        "c_grim" => "remove_card=true,extra=2",
+        "c_incantation" => "remove_card=true,extra=4",
+        "c_talisman" => "extra=\"Gold\",max_highlighted=1",
+        "c_immolate" => "remove_card=true,extra={destroy=5,dollars=20}",
+        "c_ankh" | "c_hex" => "extra=2",
+        "c_cryptid" => "extra=2,max_highlighted=1",
+        "c_deja_vu" => "extra=\"Red\",max_highlighted=1",
+        "c_trance" => "extra=\"Blue\",max_highlighted=1",
+        "c_medium" => "extra=\"Purple\",max_highlighted=1",
+        _ => "",
+    };
+
+    match consumable {
+        Consumable::PlanetCard(hand_type) => {
+            format!("hand_type={}", lua_str(hand_type.game_name()))
+        }
+        _ => config.to_string(),
+    }
+}
+
+fn voucher_config(voucher: Voucher) -> &'static str {
+    match voucher {
+        Voucher::ClearanceSale => "extra=25",
+        Voucher::Liquidation | Voucher::SeedMoney => "extra=50",
+        Voucher::Hone | Voucher::RerollSurplus | Voucher::RerollGlut => "extra=2",
+        Voucher::GlowUp | Voucher::OmenGlobe => "extra=4",
+        Voucher::CrystalBall => "extra=3",
+        Voucher::Observatory => "extra=1.5",
+        Voucher::Grabber
+        | Voucher::NachoTong
+        | Voucher::Wasteful
+        | Voucher::Recyclomancy
+        | Voucher::Hieroglyph
+        | Voucher::Petroglyph
+        | Voucher::PaintBrush
+        | Voucher::Palette => "extra=1",
+        Voucher::TarotMerchant | Voucher::PlanetMerchant => "extra=2.4,extra_disp=2",
+        Voucher::TarotTycoon | Voucher::PlanetTycoon => "extra=8,extra_disp=4",
+        Voucher::MoneyTree => "extra=100",
+        Voucher::DirectorsCut | Voucher::Retcon => "extra=10",
+        _ => "",
+    }
+}
+
+/// A fresh run's `save.jkr`, laid out like `save_run` writes it: `G.GAME` from
+/// `Game:init_game_object`, `CardArea:save()` for each area and `Blind:save()`.
+const TEMPLATE: &str = r#"return {
+    VERSION = "1.0.1o-FULL",
+    STATE = 7,
+    BACK = {name = "Red Deck", key = "b_red", pos = {x = 0, y = 0}, effect = {config = {}}},
+    BLIND = {
+        name = "", config_blind = "", boss = false, mult = 0, dollars = 0, chips = 0,
+        chip_text = "0", debuff = {}, pos = {x = 0, y = 0}, disabled = false, discards_sub = 0,
+        hands_sub = 0, hands = {}, only_hand = false, triggered = false,
+    },
+    tags = {},
+    GAME = {
+        won = false,
+        round_scores = {
+            furthest_ante = {label = "Ante", amt = 0},
+            furthest_round = {label = "Round", amt = 0},
+            hand = {label = "Best Hand", amt = 0},
+            poker_hand = {label = "Most Played Hand", amt = 0},
+            new_collection = {label = "New Discoveries", amt = 0},
+            cards_played = {label = "Cards Played", amt = 0},
+            cards_discarded = {label = "Cards Discarded", amt = 0},
+            times_rerolled = {label = "Times Rerolled", amt = 0},
+            cards_purchased = {label = "Cards Purchased", amt = 0},
+        },
+        joker_usage = {}, consumeable_usage = {}, hand_usage = {},
+        win_ante = 8, stake = 1, modifiers = {},
+        starting_params = {
+            dollars = 4, hand_size = 8, discards = 3, hands = 4, reroll_cost = 5,
+            joker_slots = 5, ante_scaling = 1, consumable_slots = 2, no_faces = false,
+            erratic_suits_and_ranks = false,
+        },
+        banned_keys = {}, round = 0, probabilities = {normal = 1},
+        bosses_used = {}, pseudorandom = {},
+        starting_deck_size = 52, ecto_minus = 1, pack_size = 2, skips = 0, STOP_USE = 0,
+        edition_rate = 1, joker_rate = 20, tarot_rate = 4, planet_rate = 4, spectral_rate = 0,
+        playing_card_rate = 0, consumeable_buffer = 0, joker_buffer = 0, discount_percent = 0,
+        interest_cap = 25, interest_amount = 1, inflation = 0, hands_played = 0,
+        unused_discards = 0, perishable_rounds = 5, rental_rate = 3,
+        blind = "\"MANUAL_REPLACE\"", selected_back = "\"MANUAL_REPLACE\"",
+        chips = 0, chips_text = "0", voucher_text = "", dollars = 4, max_jokers = 0,
+        bankrupt_at = 0, current_boss_streak = 0, base_reroll_cost = 5, blind_on_deck = "Small",
+        sort = "desc", previous_round = {dollars = 4}, tags = {}, tag_tally = 0, pool_flags = {},
+        used_jokers = {}, used_vouchers = {},
+        current_round = {
+            current_hand = {
+                chips = 0, chip_text = "0", mult = 0, mult_text = "0", chip_total = 0,
+                chip_total_text = "", handname = "", hand_level = "",
+            },
+            used_packs = {}, cards_flipped = 0, round_text = "Round ",
+            idol_card = {suit = "Spades", rank = "Ace"}, mail_card = {rank = "Ace"},
+            ancient_card = {suit = "Spades"}, castle_card = {suit = "Spades"},
+            hands_left = 4, hands_played = 0, discards_left = 3, discards_used = 0, dollars = 0,
+            reroll_cost = 5, reroll_cost_increase = 0, jokers_purchased = 0, free_rerolls = 0,
+            round_dollars = 0, dollars_to_be_earned = "!!!", most_played_poker_hand = "High Card",
+        },
+        round_resets = {
+            hands = 4, discards = 3, reroll_cost = 5, ante = 1, blind_ante = 1,
+            blind_states = {Small = "Select", Big = "Upcoming", Boss = "Upcoming"},
+            loc_blind_states = {Small = "", Big = "", Boss = ""},
+            blind_choices = {Small = "bl_small", Big = "bl_big", Boss = "bl_hook"},
+            boss_rerolled = false,
+        },
+        round_bonus = {next_hands = 0, discards = 0},
+        shop = {joker_max = 2},
+        cards_played = {
+            Ace = {suits = {}, total = 0}, King = {suits = {}, total = 0},
+            Queen = {suits = {}, total = 0}, Jack = {suits = {}, total = 0},
+            ["10"] = {suits = {}, total = 0}, ["9"] = {suits = {}, total = 0},
+            ["8"] = {suits = {}, total = 0}, ["7"] = {suits = {}, total = 0},
+            ["6"] = {suits = {}, total = 0}, ["5"] = {suits = {}, total = 0},
+            ["4"] = {suits = {}, total = 0}, ["3"] = {suits = {}, total = 0},
+            ["2"] = {suits = {}, total = 0},
+        },
+        hands = {
+            ["Flush Five"] = {visible = false, order = 1, mult = 16, chips = 160, s_mult = 16, s_chips = 160, level = 1, l_mult = 3, l_chips = 50, played = 0, played_this_round = 0,
+                example = {{"S_A", true}, {"S_A", true}, {"S_A", true}, {"S_A", true}, {"S_A", true}}},
+            ["Flush House"] = {visible = false, order = 2, mult = 14, chips = 140, s_mult = 14, s_chips = 140, level = 1, l_mult = 4, l_chips = 40, played = 0, played_this_round = 0,
+                example = {{"D_7", true}, {"D_7", true}, {"D_7", true}, {"D_4", true}, {"D_4", true}}},
+            ["Five of a Kind"] = {visible = false, order = 3, mult = 12, chips = 120, s_mult = 12, s_chips = 120, level = 1, l_mult = 3, l_chips = 35, played = 0, played_this_round = 0,
+                example = {{"S_A", true}, {"H_A", true}, {"H_A", true}, {"C_A", true}, {"D_A", true}}},
+            ["Straight Flush"] = {visible = true, order = 4, mult = 8, chips = 100, s_mult = 8, s_chips = 100, level = 1, l_mult = 4, l_chips = 40, played = 0, played_this_round = 0,
+                example = {{"S_Q", true}, {"S_J", true}, {"S_T", true}, {"S_9", true}, {"S_8", true}}},
+            ["Four of a Kind"] = {visible = true, order = 5, mult = 7, chips = 60, s_mult = 7, s_chips = 60, level = 1, l_mult = 3, l_chips = 30, played = 0, played_this_round = 0,
+                example = {{"S_J", true}, {"H_J", true}, {"C_J", true}, {"D_J", true}, {"D_3", false}}},
+            ["Full House"] = {visible = true, order = 6, mult = 4, chips = 40, s_mult = 4, s_chips = 40, level = 1, l_mult = 2, l_chips = 25, played = 0, played_this_round = 0,
+                example = {{"H_K", true}, {"C_K", true}, {"D_K", true}, {"S_2", true}, {"D_2", true}}},
+            ["Flush"] = {visible = true, order = 7, mult = 4, chips = 35, s_mult = 4, s_chips = 35, level = 1, l_mult = 2, l_chips = 15, played = 0, played_this_round = 0,
+                example = {{"H_A", true}, {"H_K", true}, {"H_T", true}, {"H_5", true}, {"H_4", true}}},
+            ["Straight"] = {visible = true, order = 8, mult = 4, chips = 30, s_mult = 4, s_chips = 30, level = 1, l_mult = 3, l_chips = 30, played = 0, played_this_round = 0,
+                example = {{"D_J", true}, {"C_T", true}, {"C_9", true}, {"S_8", true}, {"H_7", true}}},
+            ["Three of a Kind"] = {visible = true, order = 9, mult = 3, chips = 30, s_mult = 3, s_chips = 30, level = 1, l_mult = 2, l_chips = 20, played = 0, played_this_round = 0,
+                example = {{"S_T", true}, {"C_T", true}, {"D_T", true}, {"H_6", false}, {"D_5", false}}},
+            ["Two Pair"] = {visible = true, order = 10, mult = 2, chips = 20, s_mult = 2, s_chips = 20, level = 1, l_mult = 1, l_chips = 20, played = 0, played_this_round = 0,
+                example = {{"H_A", true}, {"D_A", true}, {"C_Q", false}, {"H_4", true}, {"C_4", true}}},
+            ["Pair"] = {visible = true, order = 11, mult = 2, chips = 10, s_mult = 2, s_chips = 10, level = 1, l_mult = 1, l_chips = 15, played = 0, played_this_round = 0,
+                example = {{"S_K", false}, {"S_9", true}, {"D_9", true}, {"H_6", false}, {"D_3", false}}},
+            ["High Card"] = {visible = true, order = 12, mult = 1, chips = 5, s_mult = 1, s_chips = 5, level = 1, l_mult = 1, l_chips = 10, played = 0, played_this_round = 0,
+                example = {{"S_A", true}, {"D_Q", false}, {"D_9", false}, {"C_4", false}, {"D_3", false}}},
+        },
+    },
+    cardAreas = {
+        deck = {config = {card_limit = 52, type = "deck", temp_limit = 52}, cards = {}},
+        hand = {config = {card_limit = 8, type = "hand", highlighted_limit = 5, temp_limit = 8}, cards = {}},
+        discard = {config = {card_limit = 500, type = "discard", temp_limit = 500}, cards = {}},
+        play = {config = {card_limit = 5, type = "play", temp_limit = 5}, cards = {}},
+        jokers = {config = {card_limit = 5, type = "joker", highlight_limit = 1, temp_limit = 5}, cards = {}},
+        consumeables = {config = {card_limit = 2, type = "joker", highlight_limit = 1, temp_limit = 2}, cards = {}},
+        shop_jokers = {config = {card_limit = 2, type = "shop", highlight_limit = 1, temp_limit = 2}, cards = {}},
+        shop_vouchers = {config = {card_limit = 1, type = "shop", highlight_limit = 1, temp_limit = 1}, cards = {}},
+        shop_booster = {config = {card_limit = 2, type = "shop", highlight_limit = 1, temp_limit = 2}, cards = {}},
+    },
+}"#;
@@ -1,10 +1,14 @@
 ﻿use crate::{
     blind::Blind,
+    builders::consumable::ConsumableCreator,
+    card::Rank::Rank8,
+    chance::Dice,
+    consumable::Tarot,
     event::DispatcherOrder,
     event_list::{CardScoredEventData, HandPlayedEventData},
     hands::{
         HandType,
-        HandType::{Flush, Pair, Straight, ThreeOfAKind, TwoPair},
+        HandType::{Flush, FourOfAKind, Pair, Straight, ThreeOfAKind, TwoPair},
     },
     joker::JokerType::*,
     run::{Run, RunData},
@@ -15,9 +19,10 @@ use std::{
     fmt::{Display, Formatter},
     mem::discriminant,
 };
-use strum::{Display, EnumCount, EnumIter, IntoEnumIterator};
+use strum::{Display, EnumCount, EnumIter, EnumString, IntoEnumIterator};
 
 #[derive(PartialEq, Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Joker {
     pub data: JokerInternalState,
     pub joker_type: JokerType,
@@ -56,6 +61,7 @@ impl Display for Joker {
     }
 }
 #[derive(PartialEq, Debug, Display, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum JokerEdition {
     Base,
     Foil,
@@ -64,7 +70,34 @@ pub enum JokerEdition {
     Negative,
 }
 
+impl JokerEdition {
+    pub const fn extra_cost(&self) -> f64 {
+        match self {
+            JokerEdition::Base => 0.,
+            JokerEdition::Foil => 2.,
+            JokerEdition::Holographic => 3.,
+            JokerEdition::Polychrome | JokerEdition::Negative => 5.,
+        }
+    }
+
+    pub fn scored(&self, blind: &mut Blind) {
+        match self {
+            JokerEdition::Foil => blind.chips += 50.,
+            JokerEdition::Holographic => blind.mult += 10.,
+            _ => {}
+        }
+    }
+
+    /// Polychrome multiplies after the joker it is on was scored.
+    pub fn scored_after_joker(&self, blind: &mut Blind) {
+        if *self == JokerEdition::Polychrome {
+            blind.mult *= 1.5;
+        }
+    }
+}
+
 #[derive(Debug, Default, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Stickers {
     pub eternal: bool,
     pub perishable: bool,
@@ -79,7 +112,7 @@ impl PartialEq<JokerInternalState> for JokerType {
     fn eq(&self, other: &JokerInternalState) -> bool {
         let usize_repr = INTERNALS
             .with(|internal| {
-                internal.iter().position(|variant| discriminant(variant) == discriminant(&other))
+                internal.iter().position(|variant| discriminant(variant) == discriminant(other))
             })
             .unwrap();
 
@@ -116,6 +149,24 @@ impl Joker {
         }
     }
 
+    #[must_use]
+    pub fn round_ended(&mut self) -> Option<PostExecCb> {
+        match self.joker_type {
+            Egg => {
+                self.sell_value += 3;
+                None
+            }
+            GiftCard => Some(Box::new(|_, run| {
+                run.jokers.iter_mut().for_each(|joker| joker.sell_value += 1);
+
+                let extra_value = &mut run.data.consumable_extra_value;
+                extra_value.resize(run.data.consumables.len(), 0);
+                extra_value.iter_mut().for_each(|value| *value += 1);
+            })),
+            _ => None,
+        }
+    }
+
     const PLUS_MULT_HANDTYPE_JOKERS: [(JokerType, HandType, f64); 5] = [
         (JollyJoker, Pair, 8.),
         (ZanyJoker, ThreeOfAKind, 12.),
@@ -132,14 +183,23 @@ impl Joker {
         (CraftyJoker, Flush, 80.),
     ];
 
+    const X_MULT_HANDTYPE_JOKERS: [(JokerType, HandType, f64); 5] = [
+        (TheDuo, Pair, 2.),
+        (TheTrio, ThreeOfAKind, 3.),
+        (TheFamily, FourOfAKind, 4.),
+        (TheOrder, Straight, 3.),
+        (TheTribe, Flush, 2.),
+    ];
+
     pub fn scored(
         &mut self,
         data: &mut RunData,
         blind: &mut Blind,
         event: &mut HandPlayedEventData,
+        dice: &mut Dice,
     ) -> Option<PostExecCb> {
         for (joker, hand_type, mult) in Self::PLUS_MULT_HANDTYPE_JOKERS {
-            if self.data == joker {
+            if self.joker_type == joker {
                 if event.hand.resolve(&data.cards).contains(hand_type) {
                     blind.mult += mult;
                 }
@@ -149,7 +209,7 @@ impl Joker {
         }
 
         for (joker, hand_type, chips) in Self::PLUS_CHIP_HANDTYPE_JOKERS {
-            if self.data == joker {
+            if self.joker_type == joker {
                 if event.hand.resolve(&data.cards).contains(hand_type) {
                     blind.chips += chips;
                 }
@@ -158,19 +218,23 @@ impl Joker {
             }
         }
 
-        match &self.joker_type {
-            Joker => blind.mult += 4.,
-            HalfJoker => {
-                if event.hand.len <= 3 {
-                    blind.mult += 20.
+        for (joker, hand_type, x_mult) in Self::X_MULT_HANDTYPE_JOKERS {
+            if self.joker_type == joker {
+                if event.hand.resolve(&data.cards).contains(hand_type) {
+                    blind.mult *= x_mult;
                 }
+
+                return None;
             }
+        }
+
+        match &self.joker_type {
+            Joker => blind.mult += 4.,
+            HalfJoker if event.hand.len <= 3 => blind.mult += 20.,
             Banner => blind.chips += blind.discards as f64 * 30.,
-            MysticSummit => {
-                if blind.discards == 0 {
-                    blind.mult += 15.
-                }
-            }
+            Cavendish => blind.mult *= 3.,
+            Misprint => blind.mult += dice.range(&mut data.rng, "misprint", 0, 23) as f64,
+            MysticSummit if blind.discards == 0 => blind.mult += 15.,
             RaisedFist => {
                 let smallest_rank = event.hand.resolve(&data.cards).ranks().min().unwrap() * 2;
                 blind.mult += smallest_rank as f64
@@ -188,7 +252,13 @@ impl Joker {
         None
     }
 
-    fn card_scored(&mut self, blind: &mut Blind, event: &mut CardScoredEventData) {
+    pub fn card_scored(
+        &mut self,
+        data: &mut RunData,
+        blind: &mut Blind,
+        event: &mut CardScoredEventData,
+        dice: &mut Dice,
+    ) {
         match &self.joker_type {
             SmearedJoker => {
                 event.suit.spade |= event.suit.club;
@@ -196,25 +266,27 @@ impl Joker {
                 event.suit.heart |= event.suit.diamond;
                 event.suit.diamond |= event.suit.heart;
             }
-            WrathfulJoker => {
-                if event.suit.spade {
-                    blind.mult += 3.
-                }
-            }
-            LustyJoker => {
-                if event.suit.heart {
-                    blind.mult += 3.
-                }
+            WrathfulJoker if event.suit.spade => blind.mult += 3.,
+            LustyJoker if event.suit.heart => blind.mult += 3.,
+            GluttonousJoker if event.suit.club => blind.mult += 3.,
+            GreedyJoker if event.suit.diamond => blind.mult += 3.,
+            Bloodstone if event.suit.heart && dice.one_in(&mut data.rng, "bloodstone", 2.) => {
+                blind.mult *= 1.5
             }
-            GluttonousJoker => {
-                if event.suit.club {
-                    blind.mult += 3.
-                }
+            BusinessCard if event.face_card && dice.one_in(&mut data.rng, "business", 2.) => {
+                data.money += 2.
             }
-            GreedyJoker => {
-                if event.suit.diamond {
-                    blind.mult += 3.
-                }
+            EightBall
+                if event.card.rank == Rank8
+                    && data.consumables.len() < data.consumable_slots
+                    && dice.one_in(&mut data.rng, "8ball", 4.) =>
+            {
+                let tarot = ConsumableCreator::<{ Tarot::COUNT }, Tarot>::builder()
+                    .type_key("Tarot")
+                    .origin_key("8ba")
+                    .build()
+                    .create(data);
+                data.consumables.push(tarot);
             }
             _ => {}
         }
@@ -225,6 +297,204 @@ impl Joker {
     fn cant_be_destroyed(&self) -> bool {
         self.stickers.eternal
     }
+
+    pub fn base_cost(&self) -> f64 {
+        self.joker_type.base_cost() + self.edition.extra_cost()
+    }
+}
+
+impl JokerType {
+    #[must_use]
+    pub const fn base_cost(&self) -> f64 {
+        match self {
+            CreditCard => 1.,
+            Joker => 2.,
+            JollyJoker | SlyJoker | Splash => 3.,
+            ZanyJoker | MadJoker | CrazyJoker | DrollJoker | WilyJoker | CleverJoker
+            | DeviousJoker | CraftyJoker | Misprint | ChaosTheClown | ScaryFace | AbstractJoker
+            | DelayedGratification | EvenSteven | OddTodd | Scholar | BusinessCard | Egg
+            | FacelessJoker | GreenJoker | Superposition | ToDoList | Cavendish | SquareJoker
+            | MailInRebate | Hallucination | Juggler | Drunkard | WalkieTalkie | SmileyFace
+            | Swashbuckler | HangingChad | OopsAll6s => 4.,
+            GreedyJoker | LustyJoker | WrathfulJoker | GluttonousJoker | HalfJoker | Mime
+            | Banner | MysticSummit | LoyaltyCard | EightBall | Dusk | RaisedFist | Pareidolia
+            | GrosMichel | Supernova | SpaceJoker | Runner | IceCream | BlueJoker | Hiker
+            | RedCard | Luchador | Photograph | ToTheMoon | FlashCard | Popcorn | GoldenTicket
+            | MrBones | Showman | ShootTheMoon => 5.,
+            CeremonialDagger | MarbleJoker | Hack | RideTheBus | Burglar | Blackboard
+            | SixthSense | Constellation | CardSharp | Seance | RiffRaff | Rocket | GiftCard
+            | TurtleBean | Erosion | ReservedParking | FortuneTeller | StoneJoker | GoldenJoker
+            | LuckyCat | Bull | DietCola | TradingCard | SpareTrousers | Ramen | Seltzer
+            | Castle | Acrobat | SockAndBuskin | Troubadour | Certificate | Throwback
+            | GlassJoker | FlowerPot | TheIdol | SeeingDouble | Satellite | Cartomancer => 6.,
+            FourFingers | SteelJoker | Madness | Vampire | Shortcut | Hologram | Cloud9
+            | MidasMask | SmearedJoker | RoughGem | Bloodstone | Arrowhead | OnyxAgate
+            | MerryAndy | Matador | Stuntman | DriversLicense | Bootstraps => 7.,
+            JokerStencil | Fibonacci | DNA | Vagabond | Baron | Obelisk | BaseballCard
+            | AncientJoker | WeeJoker | HitTheRoad | TheDuo | TheTrio | TheFamily | TheOrder
+            | TheTribe | InvisibleJoker | Astronomer | BurntJoker => 8.,
+            Campfire => 9.,
+            Blueprint | Brainstorm => 10.,
+            Canio | Triboulet | Yorick | Chicot | Perkeo => 20.,
+        }
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn game_name(&self) -> &'static str {
+        match self {
+            Joker => "j_joker",
+            GreedyJoker => "j_greedy_joker",
+            LustyJoker => "j_lusty_joker",
+            WrathfulJoker => "j_wrathful_joker",
+            GluttonousJoker => "j_gluttenous_joker",
+            JollyJoker => "j_jolly",
+            ZanyJoker => "j_zany",
+            MadJoker => "j_mad",
+            CrazyJoker => "j_crazy",
+            DrollJoker => "j_droll",
+            SlyJoker => "j_sly",
+            WilyJoker => "j_wily",
+            CleverJoker => "j_clever",
+            DeviousJoker => "j_devious",
+            CraftyJoker => "j_crafty",
+            HalfJoker => "j_half",
+            JokerStencil => "j_stencil",
+            FourFingers => "j_four_fingers",
+            Mime => "j_mime",
+            CreditCard => "j_credit_card",
+            CeremonialDagger => "j_ceremonial",
+            Banner => "j_banner",
+            MysticSummit => "j_mystic_summit",
+            MarbleJoker => "j_marble",
+            LoyaltyCard => "j_loyalty_card",
+            EightBall => "j_8_ball",
+            Misprint => "j_misprint",
+            Dusk => "j_dusk",
+            RaisedFist => "j_raised_fist",
+            ChaosTheClown => "j_chaos",
+            Fibonacci => "j_fibonacci",
+            SteelJoker => "j_steel_joker",
+            ScaryFace => "j_scary_face",
+            AbstractJoker => "j_abstract",
+            DelayedGratification => "j_delayed_grat",
+            Hack => "j_hack",
+            Pareidolia => "j_pareidolia",
+            GrosMichel => "j_gros_michel",
+            EvenSteven => "j_even_steven",
+            OddTodd => "j_odd_todd",
+            Scholar => "j_scholar",
+            BusinessCard => "j_business",
+            Supernova => "j_supernova",
+            RideTheBus => "j_ride_the_bus",
+            SpaceJoker => "j_space",
+            Egg => "j_egg",
+            Burglar => "j_burglar",
+            Blackboard => "j_blackboard",
+            Runner => "j_runner",
+            IceCream => "j_ice_cream",
+            DNA => "j_dna",
+            Splash => "j_splash",
+            BlueJoker => "j_blue_joker",
+            SixthSense => "j_sixth_sense",
+            Constellation => "j_constellation",
+            Hiker => "j_hiker",
+            FacelessJoker => "j_faceless",
+            GreenJoker => "j_green_joker",
+            Superposition => "j_superposition",
+            ToDoList => "j_todo_list",
+            Cavendish => "j_cavendish",
+            CardSharp => "j_card_sharp",
+            RedCard => "j_red_card",
+            Madness => "j_madness",
+            SquareJoker => "j_square",
+            Seance => "j_seance",
+            RiffRaff => "j_riff_raff",
+            Vampire => "j_vampire",
+            Shortcut => "j_shortcut",
+            Hologram => "j_hologram",
+            Vagabond => "j_vagabond",
+            Baron => "j_baron",
+            Cloud9 => "j_cloud_9",
+            Rocket => "j_rocket",
+            Obelisk => "j_obelisk",
+            MidasMask => "j_midas_mask",
+            Luchador => "j_luchador",
+            Photograph => "j_photograph",
+            GiftCard => "j_gift",
+            TurtleBean => "j_turtle_bean",
+            Erosion => "j_erosion",
+            ReservedParking => "j_reserved_parking",
+            MailInRebate => "j_mail",
+            ToTheMoon => "j_to_the_moon",
+            Hallucination => "j_hallucination",
+            FortuneTeller => "j_fortune_teller",
+            Juggler => "j_juggler",
+            Drunkard => "j_drunkard",
+            StoneJoker => "j_stone",
+            GoldenJoker => "j_golden",
+            LuckyCat => "j_lucky_cat",
+            BaseballCard => "j_baseball",
+            Bull => "j_bull",
+            DietCola => "j_diet_cola",
+            TradingCard => "j_trading",
+            FlashCard => "j_flash",
+            Popcorn => "j_popcorn",
+            SpareTrousers => "j_trousers",
+            AncientJoker => "j_ancient",
+            Ramen => "j_ramen",
+            WalkieTalkie => "j_walkie_talkie",
+            Seltzer => "j_selzer",
+            Castle => "j_castle",
+            SmileyFace => "j_smiley",
+            Campfire => "j_campfire",
+            GoldenTicket => "j_ticket",
+            MrBones => "j_mr_bones",
+            Acrobat => "j_acrobat",
+            SockAndBuskin => "j_sock_and_buskin",
+            Swashbuckler => "j_swashbuckler",
+            Troubadour => "j_troubadour",
+            Certificate => "j_certificate",
+            SmearedJoker => "j_smeared",
+            Throwback => "j_throwback",
+            HangingChad => "j_hanging_chad",
+            RoughGem => "j_rough_gem",
+            Bloodstone => "j_bloodstone",
+            Arrowhead => "j_arrowhead",
+            OnyxAgate => "j_onyx_agate",
+            GlassJoker => "j_glass",
+            Showman => "j_ring_master",
+            FlowerPot => "j_flower_pot",
+            Blueprint => "j_blueprint",
+            WeeJoker => "j_wee",
+            MerryAndy => "j_merry_andy",
+            OopsAll6s => "j_oops",
+            TheIdol => "j_idol",
+            SeeingDouble => "j_seeing_double",
+            Matador => "j_matador",
+            HitTheRoad => "j_hit_the_road",
+            TheDuo => "j_duo",
+            TheTrio => "j_trio",
+            TheFamily => "j_family",
+            TheOrder => "j_order",
+            TheTribe => "j_tribe",
+            Stuntman => "j_stuntman",
+            InvisibleJoker => "j_invisible",
+            Brainstorm => "j_brainstorm",
+            Satellite => "j_satellite",
+            ShootTheMoon => "j_shoot_the_moon",
+            DriversLicense => "j_drivers_license",
+            Cartomancer => "j_cartomancer",
+            Astronomer => "j_astronomer",
+            BurntJoker => "j_burnt",
+            Bootstraps => "j_bootstraps",
+            Canio => "j_caino",
+            Triboulet => "j_triboulet",
+            Yorick => "j_yorick",
+            Chicot => "j_chicot",
+            Perkeo => "j_perkeo",
+        }
+    }
 }
 
 pub static COMMON_JOKERS: [JokerType; 61] = [
@@ -384,7 +654,11 @@ pub static RARE_JOKERS: [JokerType; 20] = [
 pub static LEGENDARY_JOKERS: [JokerType; 5] = [Canio, Triboulet, Yorick, Chicot, Perkeo];
 
 #[repr(u8)]
-#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumCount, EnumIter, FromPrimitive, Display)]
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, EnumCount, EnumIter, FromPrimitive, Display, EnumString,
+)]
+#[strum(ascii_case_insensitive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum JokerType {
     Joker,
     GreedyJoker,
@@ -540,6 +814,7 @@ pub enum JokerType {
 
 #[repr(u8)]
 #[derive(Debug, Clone, PartialEq, Eq, EnumCount, EnumIter)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum JokerInternalState {
     None,
     DNA(bool),
@@ -4,20 +4,31 @@ pub mod blind;
 pub mod boosters;
 pub mod builders;
 pub mod card;
+pub mod chance;
 pub mod consumable;
 pub mod controller;
 pub mod decks;
+pub mod draw;
 pub mod event;
 pub mod event_list;
+pub mod forecast;
 pub mod game_state;
 pub mod hands;
+pub mod jkr;
 pub mod joker;
 pub mod misc;
+pub mod observation;
 pub mod pools;
 pub mod print_cards;
+pub mod replay;
 pub mod run;
+#[cfg(feature = "serde")]
+pub mod save;
+pub mod scoring;
+pub mod search;
 pub mod seeding;
 pub mod shop;
 pub mod stake;
 pub mod tags;
+pub mod trace;
 pub mod vouchers;
@@ -0,0 +1,128 @@
+//! What a player can see, handed to a [`Controller`](crate::controller::Controller) instead of the
+//! run itself.
+
+use crate::{
+    blind::{Blind, BlindType, BossBlindData, BossBlindType},
+    boosters::BoosterPackType,
+    card::Card,
+    consumable::Consumable,
+    decks::DeckType,
+    draw::Outs,
+    hands::HandType,
+    joker::Joker,
+    run::{Run, RunData},
+    shop::ShopItem,
+    stake::Stake,
+    tags::Tag,
+    vouchers::Voucher,
+};
+use strum::{EnumCount, IntoEnumIterator};
+
+/// The state of the run shown on screen. The rng and the order of the deck stay hidden.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Observation {
+    pub stake: Stake,
+    pub deck_type: DeckType,
+    pub ante: i32,
+    pub money: f64,
+    pub jokers: Vec<Joker>,
+    pub joker_slots: usize,
+    pub consumables: Vec<Consumable>,
+    pub consumable_slots: usize,
+    pub vouchers: Vec<Voucher>,
+    pub tags: Vec<Tag>,
+    pub hand_levels: [u32; HandType::COUNT],
+    pub blind_on_deck: BlindType,
+    pub boss: BossBlindType,
+}
+
+/// A blind as seen while playing it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BlindView {
+    pub blind_type: BlindType,
+    pub blind_data: Option<BossBlindData>,
+    pub requirement: f64,
+    pub score: f64,
+    pub hands: u32,
+    pub discards: u32,
+    pub hand_size: u32,
+    /// In the order [`BlindAction::SelectCard`](crate::controller::BlindAction) refers to them,
+    /// `None` for cards dealt face down.
+    pub held: Vec<Option<Card>>,
+    /// Positions in `held`.
+    pub selected: Vec<usize>,
+    pub deck: Outs,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShopView {
+    pub inventory: Vec<ShopItem>,
+    pub vouchers: Vec<Voucher>,
+    pub packs: Vec<BoosterPackType>,
+    pub reroll_price: f64,
+    pub free_rerolls: u32,
+}
+
+impl Observation {
+    pub fn new(run: &Run) -> Self {
+        Self::from_parts(&run.jokers, &run.data)
+    }
+
+    /// [`Observation::new`] for when the run is borrowed elsewhere, like by its blind.
+    pub fn from_parts(jokers: &[Joker], data: &RunData) -> Self {
+        Self {
+            stake: data.stake,
+            deck_type: data.deck_type,
+            ante: data.ante,
+            money: data.money,
+            jokers: jokers.to_vec(),
+            joker_slots: data.joker_slots,
+            consumables: data.consumables.clone(),
+            consumable_slots: data.consumable_slots,
+            vouchers: Voucher::iter().filter(|&voucher| data.vouchers[voucher as usize]).collect(),
+            tags: data.tags.clone(),
+            hand_levels: data.hand_levels,
+            blind_on_deck: data.blind_on_deck,
+            boss: data.this_antes_boss,
+        }
+    }
+}
+
+impl BlindView {
+    pub fn new(blind: &Blind, data: &RunData) -> Self {
+        let selected = blind.selected.card_slice();
+
+        Self {
+            blind_type: blind.blind_type,
+            blind_data: blind.blind_data.clone(),
+            requirement: blind.requirement,
+            score: blind.score,
+            hands: blind.hands,
+            discards: blind.discards,
+            hand_size: data.hand_size,
+            held: blind
+                .held
+                .iter()
+                .map(|idx| (!blind.face_down.contains(idx)).then(|| data.cards[*idx].clone()))
+                .collect(),
+            selected: (0..blind.held.len())
+                .filter(|&pos| selected.contains(&blind.held[pos]))
+                .collect(),
+            deck: Outs::new(blind, data),
+        }
+    }
+}
+
+impl ShopView {
+    pub fn new(data: &RunData) -> Self {
+        let shop = &data.shop;
+
+        Self {
+            inventory: shop.inventory.clone(),
+            vouchers: shop.vouchers.clone(),
+            packs: shop.packs.iter().flatten().copied().collect(),
+            reroll_price: shop.reroll_price,
+            free_rerolls: shop.free_rerolls,
+        }
+    }
+}
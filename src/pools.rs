@@ -1,19 +1,19 @@
 ﻿use crate::{
+    builders::card::CardCreator,
     builders::{
         consumable::ConsumableCreator,
         joker::{JokerCreator, JokerRarityMode},
     },
-    card::Edition,
+    card::{Edition, Seal},
     consumable::{PLANET_ORDER, Planet, Spectral, Tarot},
-    decks::DEFAULT_CARDS,
     joker::{
         JokerEdition,
         JokerEdition::{Base, Foil, Holographic, Negative, Polychrome},
     },
     run::{Run, RunData},
-    seeding::{math, random_element, random_idx},
+    seeding::{math, random_idx},
     shop::{ShopItem, ShopItemType},
-    vouchers::Voucher,
+    vouchers::{Voucher, Voucher::Illusion},
 };
 use num_traits::FromPrimitive;
 use strum::{EnumCount, IntoEnumIterator};
@@ -55,18 +55,25 @@ impl RunData {
     }
 
     pub fn poll_next_voucher(&mut self) -> Voucher {
+        self.poll_voucher(&format!("Voucher{}", self.ante))
+    }
+
+    pub fn poll_tag_voucher(&mut self) -> Voucher {
+        self.poll_voucher("Voucher_fromtag")
+    }
+
+    fn poll_voucher(&mut self, pool_key: &str) -> Voucher {
         let available = self.available_vouchers();
-        let pool_key = format!("Voucher{}", self.ante);
 
-        let idx = self.poll(&available, &pool_key);
+        let idx = self.poll(&available, pool_key);
         Voucher::from_usize(idx).unwrap()
     }
 
-    fn poll_joker_edition(&mut self, key: &str) -> JokerEdition {
+    pub fn poll_joker_edition(&mut self, key: &str) -> JokerEdition {
         math::randomseed(self.rng.seed(key));
 
         match math::random() {
-            poll if poll > 1. - 0.003 * self.shop.edition_rate => Negative,
+            poll if poll > 1. - 0.003 => Negative,
             poll if poll > 1. - 0.006 * self.shop.edition_rate => Polychrome,
             poll if poll > 1. - 0.02 * self.shop.edition_rate => Holographic,
             poll if poll > 1. - 0.04 * self.shop.edition_rate => Foil,
@@ -74,7 +81,38 @@ impl RunData {
         }
     }
 
-    fn poll_card_edition(&mut self, key: &str, modifier: f64) -> Edition {
+    /// Standard pack cards have a 20% chance of a seal.
+    pub fn poll_standard_seal(&mut self) -> Seal {
+        math::randomseed(self.rng.seed(&format!("stdseal{}", self.ante)));
+        if math::random() <= 0.8 {
+            return Seal::None;
+        }
+
+        math::randomseed(self.rng.seed(&format!("stdsealtype{}", self.ante)));
+        match math::random() {
+            poll if poll > 0.75 => Seal::Red,
+            poll if poll > 0.5 => Seal::Blue,
+            poll if poll > 0.25 => Seal::Gold,
+            _ => Seal::Purple,
+        }
+    }
+
+    /// Shop playing cards with Illusion have a 20% chance of a non-negative edition.
+    pub fn poll_illusion_edition(&mut self) -> Edition {
+        math::randomseed(self.rng.seed("illusion"));
+        if math::random() <= 0.8 {
+            return Edition::Base;
+        }
+
+        math::randomseed(self.rng.seed("illusion"));
+        match math::random() {
+            poll if poll > 0.85 => Edition::Polychrome,
+            poll if poll > 0.5 => Edition::Holographic,
+            _ => Edition::Foil,
+        }
+    }
+
+    pub fn poll_card_edition(&mut self, key: &str, modifier: f64) -> Edition {
         math::randomseed(self.rng.seed(key));
 
         let rate = self.shop.edition_rate * modifier;
@@ -94,6 +132,12 @@ impl Run {
         math::randomseed(data.rng.seed(&format!("cdt{}", data.ante)));
         let polled_weight = math::random() * total_weight;
 
+        let illusion = data.vouchers[Illusion as usize];
+        let enhanced = illusion && {
+            math::randomseed(data.rng.seed("illusion"));
+            math::random() > 0.6
+        };
+
         let mut check_weight = 0.;
         for item_type in ShopItemType::iter() {
             let weight = data.shop.weights[item_type as usize];
@@ -129,13 +173,24 @@ impl Run {
                     ShopItemType::Joker => JokerCreator::builder()
                         .origin_key("sho")
                         .joker_rarity(JokerRarityMode::RandomNonLegendary)
+                        .random_edition()
                         .build()
                         .create(self)
                         .into(),
 
                     ShopItemType::PlayingCard => {
-                        let seed = data.rng.seed(&format!("frontsho{}", data.ante));
-                        ShopItem::PlayingCard(random_element(&DEFAULT_CARDS, seed).clone())
+                        let edition = match illusion {
+                            true => data.poll_illusion_edition(),
+                            false => Edition::Base,
+                        };
+
+                        CardCreator::builder()
+                            .origin_key("sho")
+                            .random_enhancement(enhanced)
+                            .edition(edition)
+                            .build()
+                            .create(data)
+                            .into()
                     }
                 };
             }
@@ -0,0 +1,298 @@
+use crate::{
+    boosters::BoosterPack,
+    builders::run::RunCreator,
+    card::Card,
+    controller::{
+        ActionError, BlindAction, BlindSelectionAction, CashoutAction, Controller, PackAction,
+        ShopAction, SimulationResult,
+    },
+    decks::DeckType,
+    observation::{BlindView, Observation, ShopView},
+    run::Run,
+    stake::Stake,
+};
+use std::{
+    error::Error,
+    fmt::{Display, Formatter},
+};
+
+/// The answer to a single [`Controller`] call.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Decision {
+    Shop(Vec<ShopAction>),
+    Pack(Vec<PackAction>),
+    BlindSelection(BlindSelectionAction),
+    Blind(Vec<BlindAction>),
+    Cashout(CashoutAction),
+}
+
+/// What the controller saw when it made a decision, so a replay that drifts is caught right away.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Checkpoint {
+    pub ante: i32,
+    pub money: f64,
+    /// Empty outside of blinds, `None` for cards dealt face down.
+    pub held: Vec<Option<Card>>,
+}
+
+impl Checkpoint {
+    pub fn new(observation: &Observation, blind: Option<&BlindView>) -> Self {
+        Self {
+            ante: observation.ante,
+            money: observation.money,
+            held: blind.map_or_else(Vec::new, |blind| blind.held.clone()),
+        }
+    }
+}
+
+/// Every decision made during a run, enough to re-create it from its seed.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Replay {
+    pub seed: String,
+    pub deck: DeckType,
+    pub stake: Stake,
+    pub decisions: Vec<Decision>,
+    /// One per decision. Replays saved without them only check the kind of each decision.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub checkpoints: Vec<Checkpoint>,
+    pub result: SimulationResult,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReplayError {
+    /// The engine asked for a decision past the end of the replay.
+    Exhausted,
+    /// The engine asked for a different kind of decision than the one recorded at this index.
+    Mismatch {
+        index: usize,
+    },
+    /// The run looked different from its [`Checkpoint`] when this decision was asked for.
+    StateMismatch {
+        index: usize,
+    },
+    /// The run ended before these many decisions were used.
+    Unused(usize),
+    Diverged {
+        expected: Box<SimulationResult>,
+        actual: Box<SimulationResult>,
+    },
+}
+
+impl Display for ReplayError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReplayError::Exhausted => write!(f, "ran out of recorded decisions"),
+            ReplayError::Mismatch { index } => write!(f, "decision {index} is of the wrong kind"),
+            ReplayError::StateMismatch { index } => {
+                write!(f, "the run drifted before decision {index}")
+            }
+            ReplayError::Unused(count) => write!(f, "{count} decisions were never used"),
+            ReplayError::Diverged { expected, actual } => {
+                write!(f, "expected the run to end with {expected:?}, got {actual:?}")
+            }
+        }
+    }
+}
+
+impl Error for ReplayError {}
+
+/// Wraps a controller and records its decisions.
+pub struct Recorder<C> {
+    pub controller: C,
+    pub decisions: Vec<Decision>,
+    pub checkpoints: Vec<Checkpoint>,
+}
+
+impl<C: Controller> Recorder<C> {
+    pub fn new(controller: C) -> Self {
+        Self { controller, decisions: Vec::new(), checkpoints: Vec::new() }
+    }
+}
+
+impl<C: Controller> Controller for Recorder<C> {
+    fn shop(&mut self, observation: &Observation, shop: &ShopView) -> Vec<ShopAction> {
+        let actions = self.controller.shop(observation, shop);
+        self.checkpoints.push(Checkpoint::new(observation, None));
+        self.decisions.push(Decision::Shop(actions.clone()));
+        actions
+    }
+
+    fn pack(&mut self, observation: &Observation, pack: &BoosterPack) -> Vec<PackAction> {
+        let actions = self.controller.pack(observation, pack);
+        self.checkpoints.push(Checkpoint::new(observation, None));
+        self.decisions.push(Decision::Pack(actions.clone()));
+        actions
+    }
+
+    fn blind_selection(&mut self, observation: &Observation) -> BlindSelectionAction {
+        let action = self.controller.blind_selection(observation);
+        self.checkpoints.push(Checkpoint::new(observation, None));
+        self.decisions.push(Decision::BlindSelection(action));
+        action
+    }
+
+    fn blind(&mut self, observation: &Observation, blind: &BlindView) -> Vec<BlindAction> {
+        let actions = self.controller.blind(observation, blind);
+        self.checkpoints.push(Checkpoint::new(observation, Some(blind)));
+        self.decisions.push(Decision::Blind(actions.clone()));
+        actions
+    }
+
+    fn cashout(&mut self, observation: &Observation) -> CashoutAction {
+        let action = self.controller.cashout(observation);
+        self.checkpoints.push(Checkpoint::new(observation, None));
+        self.decisions.push(Decision::Cashout(action));
+        action
+    }
+
+    fn rejected(&mut self, error: ActionError) {
+        self.controller.rejected(error)
+    }
+}
+
+/// Feeds recorded decisions back. Once something goes wrong it aborts the run as soon as it can.
+struct Replayer<'a> {
+    decisions: &'a [Decision],
+    checkpoints: &'a [Checkpoint],
+    next: usize,
+    error: Option<ReplayError>,
+}
+
+impl Replayer<'_> {
+    fn next(&mut self, checkpoint: Checkpoint) -> Option<&Decision> {
+        if self.error.is_some() {
+            return None;
+        }
+
+        let Some(decision) = self.decisions.get(self.next) else {
+            self.error = Some(ReplayError::Exhausted);
+            return None;
+        };
+
+        if self.checkpoints.get(self.next).is_some_and(|recorded| *recorded != checkpoint) {
+            self.error = Some(ReplayError::StateMismatch { index: self.next });
+            return None;
+        }
+
+        self.next += 1;
+        Some(decision)
+    }
+
+    fn mismatch(&mut self) {
+        self.error = Some(ReplayError::Mismatch { index: self.next - 1 });
+    }
+}
+
+impl Controller for Replayer<'_> {
+    fn shop(&mut self, observation: &Observation, _: &ShopView) -> Vec<ShopAction> {
+        match self.next(Checkpoint::new(observation, None)) {
+            Some(Decision::Shop(actions)) => actions.clone(),
+            Some(_) => {
+                self.mismatch();
+                vec![ShopAction::ExitShop]
+            }
+            None => vec![ShopAction::ExitShop],
+        }
+    }
+
+    fn pack(&mut self, observation: &Observation, _: &BoosterPack) -> Vec<PackAction> {
+        match self.next(Checkpoint::new(observation, None)) {
+            Some(Decision::Pack(actions)) => actions.clone(),
+            Some(_) => {
+                self.mismatch();
+                vec![PackAction::Skip]
+            }
+            None => vec![PackAction::Skip],
+        }
+    }
+
+    fn blind_selection(&mut self, observation: &Observation) -> BlindSelectionAction {
+        match self.next(Checkpoint::new(observation, None)) {
+            Some(Decision::BlindSelection(action)) => *action,
+            Some(_) => {
+                self.mismatch();
+                BlindSelectionAction::PlayBlind
+            }
+            None => BlindSelectionAction::PlayBlind,
+        }
+    }
+
+    fn blind(&mut self, observation: &Observation, blind: &BlindView) -> Vec<BlindAction> {
+        match self.next(Checkpoint::new(observation, Some(blind))) {
+            Some(Decision::Blind(actions)) => actions.clone(),
+            Some(_) => {
+                self.mismatch();
+                vec![BlindAction::Abort]
+            }
+            None => vec![BlindAction::Abort],
+        }
+    }
+
+    fn cashout(&mut self, observation: &Observation) -> CashoutAction {
+        match self.next(Checkpoint::new(observation, None)) {
+            Some(Decision::Cashout(action)) => *action,
+            Some(_) => {
+                self.mismatch();
+                CashoutAction::ReturnToShop
+            }
+            None => CashoutAction::ReturnToShop,
+        }
+    }
+}
+
+impl Replay {
+    /// Simulates a run created by `creator`, recording every decision of `controller`.
+    pub fn record(creator: RunCreator, controller: impl Controller) -> Replay {
+        let run = creator.create();
+        let (seed, deck, stake) = (run.data.rng.seed.clone(), run.data.deck_type, run.data.stake);
+
+        let mut recorder = Recorder::new(controller);
+        let result = run.simulate(&mut recorder);
+
+        let Recorder { decisions, checkpoints, .. } = recorder;
+        Replay { seed, deck, stake, decisions, checkpoints, result }
+    }
+
+    /// The run as it was before the first decision.
+    #[must_use]
+    pub fn run(&self) -> Run {
+        RunCreator::builder()
+            .seed(self.seed.clone())
+            .deck(self.deck)
+            .stake(self.stake)
+            .build()
+            .create()
+    }
+
+    /// Re-executes every decision, checking the run against each [`Checkpoint`] on the way
+    /// and that it ends the same way.
+    pub fn verify(&self) -> Result<(), ReplayError> {
+        let mut replayer = Replayer {
+            decisions: &self.decisions,
+            checkpoints: &self.checkpoints,
+            next: 0,
+            error: None,
+        };
+        let result = self.run().simulate(&mut replayer);
+
+        if let Some(error) = replayer.error {
+            return Err(error);
+        }
+
+        if replayer.next != self.decisions.len() {
+            return Err(ReplayError::Unused(self.decisions.len() - replayer.next));
+        }
+
+        if result != self.result {
+            return Err(ReplayError::Diverged {
+                expected: Box::new(self.result.clone()),
+                actual: Box::new(result),
+            });
+        }
+
+        Ok(())
+    }
+}
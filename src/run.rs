@@ -5,18 +5,27 @@ use crate::{
         BossBlindType,
         BossBlindType::{TheManacle, TheNeedle, TheWall, TheWater},
     },
+    boosters::BoosterPack,
     card::Card,
+    chance::Seeded,
     consumable::{Consumable, Planet},
     controller::{
-        BlindAction, BlindSelectionAction, CashoutAction, Controller, ShopAction, SimulationResult,
+        ActionError, BlindAction, BlindSelectionAction, CashoutAction, Controller, PackAction,
+        ShopAction, SimulationResult,
     },
     decks::DeckType,
     event::Event,
     event_list::HandPlayedEventData,
     game_state::GameState,
     hands::{Hand, HandType},
-    joker::{Joker, JokerType::Chicot},
-    misc,
+    joker::{
+        Joker,
+        JokerEdition::Negative,
+        JokerType,
+        JokerType::{Chicot, CreditCard},
+    },
+    observation::{BlindView, Observation, ShopView},
+    scoring::{Callback, score_hand_observed, scoring_cards},
     seeding::{BalatroRng, shuffle},
     shop::{
         Shop, ShopItemType,
@@ -26,6 +35,8 @@ use crate::{
         Stake,
         Stake::{Green, Purple},
     },
+    tags::Tag,
+    trace::{NoObserver, Observer, TraceEvent},
     vouchers::Voucher,
 };
 use BossBlindType::VioletVessel;
@@ -34,12 +45,16 @@ use itertools::Itertools;
 use std::{cmp::max, mem::take, ops::Not};
 use strum::EnumCount;
 
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Run {
     pub data: RunData,
     pub jokers: Vec<Joker>,
     pub game_state: GameState,
 }
 
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RunData {
     pub stake: Stake,
     pub rng: BalatroRng,
@@ -48,6 +63,8 @@ pub struct RunData {
     pub deck_type: DeckType,
     pub joker_slots: usize,
     pub consumables: Vec<Consumable>,
+    /// Sell value Gift Card added to each consumable, missing entries are 0.
+    pub consumable_extra_value: Vec<u32>,
     pub consumable_slots: usize,
     pub vouchers: [bool; Voucher::COUNT],
     pub starting_hands: u32,
@@ -62,7 +79,15 @@ pub struct RunData {
     pub planet_unlocked: [bool; Planet::COUNT],
     pub times_boss_used: [usize; BossBlindType::COUNT],
     pub this_antes_boss: BossBlindType,
+    pub boss_rerolled: bool,
+    pub blind_on_deck: BlindType,
     pub showman: bool,
+    pub tags: Vec<Tag>,
+    pub ante_scaling: f64,
+    pub money_per_hand: f64,
+    pub money_per_discard: f64,
+    pub interest_cap: f64,
+    pub no_interest: bool,
 }
 
 pub static BLIND_REQUIREMENTS: [[f64; 3]; 9] = [
@@ -82,6 +107,11 @@ impl RunData {
         cards.iter().map(|idx| &self.cards[*idx]).collect()
     }
 
+    pub fn consumable_sell_value(&self, idx: usize) -> f64 {
+        let extra_value = self.consumable_extra_value.get(idx).copied().unwrap_or(0);
+        self.shop.sell_value(self.consumables[idx].base_cost()) + extra_value as f64
+    }
+
     pub fn base_chip_requirement(&self) -> f64 {
         let idx = match self.stake {
             stake if stake >= Purple => 2,
@@ -89,22 +119,77 @@ impl RunData {
             _ => 0,
         };
 
-        match self.ante {
+        let amount = match self.ante {
             ante if ante <= 8 => BLIND_REQUIREMENTS[ante.max(0) as usize][idx],
             ante => {
                 let endless_mode_ante = ante as f64 - 8.;
                 let first_pow = (0.75 * endless_mode_ante).powf(1. + 0.2 * endless_mode_ante);
                 BLIND_REQUIREMENTS[8][idx] * (1.6 + first_pow).powf(endless_mode_ante)
             }
+        };
+
+        amount * self.ante_scaling
+    }
+
+    pub fn interest(&self) -> f64 {
+        if self.no_interest {
+            return 0.;
+        }
+
+        (self.money / 5.).floor().clamp(0., self.interest_cap / 5.)
+    }
+
+    pub fn round_earnings(&self, blind: &Blind) -> f64 {
+        blind.blind_type.reward(self.stake)
+            + blind.hands as f64 * self.money_per_hand
+            + blind.discards as f64 * self.money_per_discard
+            + self.interest()
+    }
+
+    /// Applies everything that happens after `blind` was beaten and returns the cash out.
+    pub fn end_round(&mut self, blind: &Blind) -> f64 {
+        let earnings = self.round_earnings(blind);
+        self.deck_type.blind_defeated(self, blind.blind_type);
+
+        self.blind_on_deck = match blind.blind_type {
+            Small => Big,
+            Big => Boss(self.this_antes_boss),
+            Boss(_) => {
+                self.ante += 1;
+                self.this_antes_boss = self.current_boss();
+                self.boss_rerolled = false;
+                Small
+            }
+        };
+
+        earnings
+    }
+
+    /// Removes one `tag` and returns whether there was one to remove.
+    pub fn take_tag(&mut self, tag: Tag) -> bool {
+        match self.tags.iter().position(|held| *held == tag) {
+            Some(idx) => {
+                self.tags.remove(idx);
+                true
+            }
+            None => false,
         }
     }
 
+    /// Levels `hand` up or down, along with its base chips and mult.
     pub fn change_hand_level(&mut self, hand: HandType, amount: i32) {
-        let hand_level = &mut self.hand_levels[hand as usize];
-        *hand_level = max(*hand_level as i32 + amount, 1) as u32;
+        let idx = hand as usize;
+        let old_level = self.hand_levels[idx];
+        self.hand_levels[idx] = max(old_level as i32 + amount, 1) as u32;
+
+        let levels = self.hand_levels[idx] as i64 - old_level as i64;
+        let chips = self.base_chips[idx] as i64 + levels * hand.level_chips() as i64;
+        let mult = self.base_mult[idx] as i64 + levels * hand.level_mult() as i64;
+        self.base_chips[idx] = max(chips, 0) as u64;
+        self.base_mult[idx] = max(mult, 1) as u64;
     }
 
-    pub(crate) fn is_most_played_hand(&self, hand_in_question: HandType) -> bool {
+    pub fn is_most_played_hand(&self, hand_in_question: HandType) -> bool {
         let times_hand_was_played = self.times_played[hand_in_question as usize];
 
         self.times_played
@@ -123,7 +208,10 @@ impl RunData {
             Grabber | NachoTong => self.starting_hands += 1,
             Wasteful | Recyclomancy => self.starting_discards += 1,
             PaintBrush | Palette => self.hand_size += 1,
-            RerollSurplus | RerollGlut => self.shop.reroll_price -= 2.,
+            RerollSurplus | RerollGlut => {
+                self.shop.base_reroll_price -= 2.;
+                self.shop.update_reroll_price();
+            }
             ClearanceSale | Liquidation => self.shop.price_multiplier -= 0.25,
             Antimatter => self.joker_slots += 1,
             Hieroglyph => {
@@ -141,9 +229,13 @@ impl RunData {
             PlanetTycoon => self.shop.weights[ShopItemType::Planet as usize] = 32.,
             Overstock | OverstockPlus => self.shop.size += 1,
             Hone => self.shop.edition_rate = 2.,
-            GlowUp => self.shop.edition_rate = 5.,
-            OmenGlobe => {}
-            _ => {}
+            GlowUp => self.shop.edition_rate = 4.,
+            SeedMoney => self.interest_cap = 50.,
+            MoneyTree => self.interest_cap = 100.,
+            // These are checked whenever they matter: Telescope and Omen Globe while opening
+            // packs, Observatory while scoring, Illusion in the shop and Director's Cut and
+            // Retcon when rerolling the boss. Blank only unlocks Antimatter.
+            OmenGlobe | Telescope | Observatory | Illusion | DirectorsCut | Retcon | Blank => {}
         }
     }
 }
@@ -183,6 +275,7 @@ impl Run {
             score: 0.,
             cards,
             held: Vec::new(),
+            face_down: Vec::new(),
             selected: Hand::default(),
             blind_data: blind_type.default_data(),
             blind_type,
@@ -191,7 +284,7 @@ impl Run {
             discards,
         };
 
-        blind.draw(&self.data);
+        blind.draw(&self.jokers, &mut self.data);
 
         let event = Event::BlindEntered;
         let event_usize = event as usize;
@@ -208,112 +301,255 @@ impl Run {
         self.game_state = GameState::Blind(blind);
     }
 
+    pub fn round_ended(&mut self) {
+        let event_usize = Event::RoundEnded as usize;
+
+        self.jokers
+            .iter_mut()
+            .enumerate()
+            .sorted_by_key(|(_, joker)| joker.dispatcher_order.events[event_usize])
+            .filter_map(|(idx, joker)| joker.round_ended().map(|cb| (idx, cb)))
+            .collect_vec()
+            .into_iter()
+            .for_each(|(idx, mut callback)| callback(idx, self));
+    }
+
     pub fn get_chicot_count(&self) -> u32 {
         self.jokers.iter().filter(|joker| joker.joker_type == Chicot).count() as _
     }
 
-    pub fn simulate(mut self, mut controller: impl Controller) -> SimulationResult {
+    pub fn has_joker(&self, joker_type: JokerType) -> bool {
+        self.jokers.iter().any(|joker| joker.joker_type == joker_type && !joker.debuffed)
+    }
+
+    pub fn free_joker_slots(&self) -> usize {
+        let used = self.jokers.iter().filter(|joker| joker.edition != Negative).count();
+        self.data.joker_slots.saturating_sub(used)
+    }
+
+    /// How far the money may go below zero, which Credit Card raises to $20.
+    pub fn bankrupt_at(&self) -> f64 {
+        match self.has_joker(CreditCard) {
+            true => -20.,
+            false => 0.,
+        }
+    }
+
+    pub fn can_afford(&self, cost: f64) -> bool {
+        self.data.money - cost >= self.bankrupt_at()
+    }
+
+    /// Independent copy of the run, including every rng stream, to branch off at a decision.
+    ///
+    /// Lua's `math.random` is reseeded before every use, so no state lives outside the run.
+    #[must_use]
+    pub fn fork(&self) -> Run {
+        self.clone()
+    }
+
+    pub fn simulate(self, controller: impl Controller) -> SimulationResult {
+        self.simulate_observed(controller, NoObserver)
+    }
+
+    /// [`Run::simulate`], reporting every step of the run to `observer`.
+    pub fn simulate_observed(
+        mut self,
+        mut controller: impl Controller,
+        mut observer: impl Observer,
+    ) -> SimulationResult {
+        if let GameState::Blind(blind) = &self.game_state {
+            self.observe_blind_entered(blind, &mut observer);
+        }
+
         loop {
-            type Callback = Box<dyn FnMut(&mut Run)>;
             let mut cbs: Vec<Callback> = Vec::new();
+            let money = self.data.money;
+            let boss = self.data.this_antes_boss;
 
             match &mut self.game_state {
                 GameState::Shop => {
-                    for action in controller.shop() {
-                        match action {
+                    let observation = Observation::new(&self);
+                    for action in controller.shop(&observation, &ShopView::new(&self.data)) {
+                        let result = match action {
+                            ShopAction::BuyItem(idx) => self.buy_shop_item(idx).map(drop),
+                            ShopAction::Reroll => {
+                                self.reroll().map(|_| self.observe_shop(&mut observer))
+                            }
+                            ShopAction::BuyVoucher(idx) => self.buy_voucher(idx).map(drop),
+                            ShopAction::SellJoker(idx) => self.sell_joker(idx).map(drop),
+                            ShopAction::SellConsumable(idx) => self.sell_consumable(idx).map(drop),
+                            ShopAction::BuyPack(idx) => self.buy_booster_pack(idx).map(|pack| {
+                                self.pick_from_pack(pack, &mut controller, &mut observer)
+                            }),
                             ShopAction::ExitShop => {
                                 self.game_state = GameState::BlindSelection;
+                                Ok(())
                             }
+                        };
+
+                        if let Err(error) = result {
+                            reject(&mut controller, &mut observer, error);
                         }
                     }
                 }
-                GameState::BlindSelection => match controller.blind_selection() {
-                    BlindSelectionAction::PlayBlind => {
-                        self.new_blind(Small);
+                GameState::BlindSelection => {
+                    match controller.blind_selection(&Observation::new(&self)) {
+                        BlindSelectionAction::PlayBlind => {
+                            self.new_blind(self.data.blind_on_deck);
+                            if let GameState::Blind(blind) = &self.game_state {
+                                self.observe_blind_entered(blind, &mut observer);
+                            }
+                        }
                     }
-                },
+                }
                 GameState::Blind(blind) => {
                     if blind.hands == 0 || blind.held.is_empty() {
                         return SimulationResult::Lost { blind: take(blind) };
                     }
 
-                    for action in controller.blind(blind, &mut self.data) {
+                    let observation = Observation::from_parts(&self.jokers, &self.data);
+                    let view = BlindView::new(blind, &self.data);
+                    for action in controller.blind(&observation, &view) {
                         match action {
-                            BlindAction::SelectCard(card) => blind.select(card),
+                            BlindAction::SelectCard(card) => {
+                                if let Err(error) = blind.select(card) {
+                                    reject(&mut controller, &mut observer, error);
+                                }
+                            }
                             BlindAction::Discard => {
-                                if blind.discard().is_some() {
-                                    todo!()
+                                if let Err(error) = blind.discard() {
+                                    reject(&mut controller, &mut observer, error);
+                                    continue;
                                 }
+
+                                let held = blind.held.len();
+                                blind.draw(&self.jokers, &mut self.data);
+                                observer
+                                    .observe(&TraceEvent::CardsDrawn(blind.held[held..].to_vec()));
                             }
                             BlindAction::Play => {
-                                let Some(hand) = blind.prepare_play(&self.data) else {
-                                    continue;
+                                let hand = match blind.prepare_play(&self.data) {
+                                    Ok(hand) => hand,
+                                    Err(error) => {
+                                        reject(&mut controller, &mut observer, error);
+                                        continue;
+                                    }
                                 };
 
                                 let mut event_data = HandPlayedEventData { hand, allowed: true };
 
                                 blind.hand_played(&mut self.data, &mut event_data);
 
-                                let cards = event_data.hand.resolve(&self.data.cards).0;
-                                println!("Played {}", cards.iter().join(", "));
-
-                                for card in cards {
-                                    blind.score += card.chips as f64;
-                                }
-
-                                let event_usize = Event::Scored as usize;
-                                cbs = self
-                                    .jokers
-                                    .iter_mut()
-                                    .enumerate()
-                                    .sorted_by_key(|(_, joker)| {
-                                        joker.dispatcher_order.events[event_usize]
-                                    })
-                                    .map_while(|(idx, joker)| {
-                                        event_data.allowed.then(|| {
-                                            joker
-                                                .scored(&mut self.data, blind, &mut event_data)
-                                                .map(|x| {
-                                                    Box::new(misc::curry_mut(x, idx)) as Callback
-                                                })
-                                        })
-                                    })
-                                    .flatten()
-                                    .collect_vec();
+                                let resolved = event_data.hand.resolve(&self.data.cards);
+                                let cards = event_data.hand.card_slice().to_vec();
+                                observer.observe(&TraceEvent::HandPlayed {
+                                    hand_type: resolved.hand_type(),
+                                    scoring: scoring_cards(
+                                        &self.jokers,
+                                        &self.data.cards,
+                                        &event_data.hand,
+                                    ),
+                                    cards,
+                                });
+
+                                cbs = score_hand_observed(
+                                    &mut self.jokers,
+                                    &mut self.data,
+                                    blind,
+                                    &mut event_data,
+                                    &mut Seeded,
+                                    &mut observer,
+                                );
 
                                 if !event_data.allowed {
                                     continue;
                                 }
 
-                                blind.draw(&self.data);
-                                blind.score += blind.chips * blind.mult;
+                                let held = blind.held.len();
+                                blind.draw(&self.jokers, &mut self.data);
+                                observer
+                                    .observe(&TraceEvent::CardsDrawn(blind.held[held..].to_vec()));
 
                                 if blind.score >= blind.requirement {
-                                    if matches!(blind.blind_type, Boss(_)) {
-                                        if self.data.ante == 8 {
-                                            return SimulationResult::Won;
-                                        }
-
-                                        self.data.ante += 1;
+                                    if matches!(blind.blind_type, Boss(_)) && self.data.ante == 8 {
+                                        return SimulationResult::Won;
                                     }
 
-                                    cbs.push(Box::new(|run| run.game_state = GameState::CashOut))
+                                    let earnings = self.data.end_round(blind);
+                                    cbs.push(Box::new(move |run| {
+                                        run.round_ended();
+                                        run.game_state = GameState::CashOut { earnings }
+                                    }))
                                 }
                             }
                             BlindAction::Abort => return SimulationResult::Aborted,
                         }
                     }
                 }
-                GameState::CashOut => match controller.cashout() {
-                    CashoutAction::ReturnToShop => {
-                        self.game_state = GameState::Shop;
+                GameState::CashOut { earnings } => {
+                    match controller.cashout(&Observation::from_parts(&self.jokers, &self.data)) {
+                        CashoutAction::ReturnToShop => {
+                            self.data.money += *earnings;
+                            self.game_state = GameState::Shop;
+                            // The next ante's voucher is rolled once its Boss is beaten
+                            self.enter_shop(self.data.blind_on_deck == Small);
+                            self.observe_shop(&mut observer);
+                        }
                     }
-                },
+                }
             };
 
             for mut cb in cbs {
                 cb(&mut self);
             }
+
+            if self.data.money != money {
+                observer.observe(&TraceEvent::MoneyChanged { from: money, to: self.data.money });
+            }
+
+            if self.data.this_antes_boss != boss {
+                observer.observe(&TraceEvent::BossChosen(self.data.this_antes_boss));
+            }
         }
     }
+
+    fn observe_blind_entered(&self, blind: &Blind, observer: &mut impl Observer) {
+        observer.observe(&TraceEvent::BlindEntered {
+            blind_type: blind.blind_type,
+            requirement: blind.requirement,
+        });
+        observer.observe(&TraceEvent::CardsDrawn(blind.held.clone()));
+    }
+
+    /// Lets `controller` pick from a pack that was just bought.
+    fn pick_from_pack(
+        &mut self,
+        mut pack: BoosterPack,
+        controller: &mut impl Controller,
+        observer: &mut impl Observer,
+    ) {
+        for action in controller.pack(&Observation::new(self), &pack) {
+            match action {
+                PackAction::Pick(idx) => {
+                    if let Err(error) = self.pick_from_booster_pack(&mut pack, idx) {
+                        reject(controller, observer, error);
+                    }
+                }
+                PackAction::Skip => break,
+            }
+        }
+    }
+
+    fn observe_shop(&self, observer: &mut impl Observer) {
+        observer.observe(&TraceEvent::ShopGenerated {
+            inventory: self.data.shop.inventory.clone(),
+            packs: self.data.shop.packs.iter().flatten().copied().collect(),
+            vouchers: self.data.shop.vouchers.clone(),
+        });
+    }
+}
+
+fn reject(controller: &mut impl Controller, observer: &mut impl Observer, error: ActionError) {
+    observer.observe(&TraceEvent::ActionRejected(error));
+    controller.rejected(error);
 }
@@ -0,0 +1,118 @@
+use crate::{replay::Replay, run::Run};
+use serde::{Deserialize, Serialize};
+use std::{
+    error::Error,
+    fmt::{Display, Formatter},
+};
+
+/// Bumped whenever the serialized layout of [`Run`] changes.
+pub const SAVE_VERSION: u32 = 1;
+
+#[derive(Debug)]
+pub enum SaveError {
+    Json(serde_json::Error),
+    Binary(postcard::Error),
+    /// The save was written by a different [`SAVE_VERSION`].
+    Version(u32),
+}
+
+impl Display for SaveError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SaveError::Json(err) => write!(f, "invalid JSON save: {err}"),
+            SaveError::Binary(err) => write!(f, "invalid binary save: {err}"),
+            SaveError::Version(version) => {
+                write!(f, "save version {version} is not supported, expected {SAVE_VERSION}")
+            }
+        }
+    }
+}
+
+impl Error for SaveError {}
+
+impl From<serde_json::Error> for SaveError {
+    fn from(err: serde_json::Error) -> Self {
+        SaveError::Json(err)
+    }
+}
+
+impl From<postcard::Error> for SaveError {
+    fn from(err: postcard::Error) -> Self {
+        SaveError::Binary(err)
+    }
+}
+
+#[derive(Serialize)]
+struct SaveRef<'a> {
+    version: u32,
+    run: &'a Run,
+}
+
+#[derive(Deserialize)]
+struct VersionOnly {
+    version: u32,
+}
+
+#[derive(Deserialize)]
+struct Save {
+    run: Run,
+}
+
+#[derive(Serialize)]
+struct ReplayRef<'a> {
+    version: u32,
+    replay: &'a Replay,
+}
+
+#[derive(Deserialize)]
+struct ReplayFile {
+    replay: Replay,
+}
+
+fn check_version(json: &str) -> Result<(), SaveError> {
+    let VersionOnly { version } = serde_json::from_str(json)?;
+    if version != SAVE_VERSION {
+        return Err(SaveError::Version(version));
+    }
+
+    Ok(())
+}
+
+impl Run {
+    pub fn to_json(&self) -> Result<String, SaveError> {
+        Ok(serde_json::to_string(&SaveRef { version: SAVE_VERSION, run: self })?)
+    }
+
+    pub fn from_json(json: &str) -> Result<Run, SaveError> {
+        check_version(json)?;
+        let Save { run } = serde_json::from_str(json)?;
+        Ok(run)
+    }
+
+    /// Compact postcard encoding, prefixed with the [`SAVE_VERSION`].
+    pub fn to_bytes(&self) -> Result<Vec<u8>, SaveError> {
+        let bytes = postcard::to_stdvec(&SAVE_VERSION)?;
+        Ok(postcard::to_extend(self, bytes)?)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Run, SaveError> {
+        let (version, run) = postcard::take_from_bytes::<u32>(bytes)?;
+        if version != SAVE_VERSION {
+            return Err(SaveError::Version(version));
+        }
+
+        Ok(postcard::from_bytes(run)?)
+    }
+}
+
+impl Replay {
+    pub fn to_json(&self) -> Result<String, SaveError> {
+        Ok(serde_json::to_string(&ReplayRef { version: SAVE_VERSION, replay: self })?)
+    }
+
+    pub fn from_json(json: &str) -> Result<Replay, SaveError> {
+        check_version(json)?;
+        let ReplayFile { replay } = serde_json::from_str(json)?;
+        Ok(replay)
+    }
+}
@@ -0,0 +1,429 @@
+use crate::{
+    blind::Blind,
+    card::{
+        Card, Edition,
+        Enhancement::{BonusCard, GlassCard, LuckyCard, MultCard, SteelCard, StoneCard},
+        MultiSuit, Seal,
+    },
+    chance::{Dice, Enumerator, Roller, Sampler, Seeded},
+    controller::ActionError,
+    event::Event,
+    event_list::{CardScoredEventData, HandPlayedEventData},
+    hands::{Hand, HandType, ResolvedHand},
+    joker::{
+        Joker,
+        JokerType::{Bloodstone, BusinessCard, EightBall, Misprint, SpaceJoker, Splash},
+    },
+    misc,
+    run::{Run, RunData},
+    trace::{NoObserver, Observer, Source, TraceEvent},
+};
+use itertools::Itertools;
+
+pub type Callback = Box<dyn FnMut(&mut Run)>;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Contribution {
+    pub source: Source,
+    pub chips: f64,
+    pub mult: f64,
+}
+
+/// What a hand would score, see [`score_breakdown`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScoreBreakdown {
+    pub hand_type: HandType,
+    /// False if the boss blind wouldn't let this hand score.
+    pub allowed: bool,
+    /// The played cards that scored, see [`Hand::scoring`]. Indices into `RunData::cards`.
+    pub scoring: Vec<usize>,
+    pub chips: f64,
+    pub mult: f64,
+    pub score: f64,
+    /// In scoring order.
+    pub contributions: Vec<Contribution>,
+    /// Sources with a random effect.
+    pub chance: Vec<Source>,
+}
+
+/// One combination of random outcomes, see [`score_distribution`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Outcome {
+    pub probability: f64,
+    pub score: f64,
+    /// Money earned while scoring, like Lucky Cards and Business Card.
+    pub money: f64,
+    /// Keys of the chances that hit, like `lucky_mult` or `glass` for a Glass Card shattering.
+    pub triggered: Vec<&'static str>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScoreDistribution {
+    pub outcomes: Vec<Outcome>,
+}
+
+impl ScoreDistribution {
+    pub fn mean(&self) -> f64 {
+        self.outcomes.iter().map(|outcome| outcome.probability * outcome.score).sum()
+    }
+
+    pub fn variance(&self) -> f64 {
+        let mean = self.mean();
+        self.outcomes
+            .iter()
+            .map(|outcome| outcome.probability * (outcome.score - mean).powi(2))
+            .sum()
+    }
+
+    pub fn probability_at_least(&self, score: f64) -> f64 {
+        self.outcomes.iter().filter(|outcome| outcome.score >= score).map(|o| o.probability).sum()
+    }
+
+    /// The chance of the hand beating what's left of `blind.requirement`.
+    pub fn probability_to_clear(&self, blind: &Blind) -> f64 {
+        self.probability_at_least(blind.requirement - blind.score)
+    }
+
+    /// The chance of `key` hitting at least once, like `glass` for any Glass Card shattering.
+    pub fn probability_of(&self, key: &str) -> f64 {
+        let hit = |outcome: &&Outcome| outcome.triggered.contains(&key);
+        self.outcomes.iter().filter(hit).map(|outcome| outcome.probability).sum()
+    }
+}
+
+/// Outcome combinations beyond which [`score_distribution`] gives up.
+pub const MAX_OUTCOMES: usize = 1 << 16;
+
+/// Plays `hand` on copies of the run and the blind.
+fn score_copy(
+    jokers: &[Joker],
+    data: &RunData,
+    blind: &Blind,
+    hand: &Hand,
+    roller: &mut dyn Roller,
+) -> Option<(ScoreBreakdown, Outcome)> {
+    let mut jokers = jokers.to_vec();
+    let mut data = data.clone();
+    let mut blind = Blind { selected: hand.clone(), score: 0., ..blind.clone() };
+
+    match blind.can_play(&data) {
+        Ok(()) | Err(ActionError::HandNotAllowed(_)) => {}
+        Err(_) => return None,
+    }
+
+    let hand = blind.take_selected(&data);
+    let hand_type = hand.resolve(&data.cards).hand_type();
+    let mut event = HandPlayedEventData { hand, allowed: true };
+    blind.hand_played(&mut data, &mut event);
+
+    let scoring = scoring_cards(&jokers, &data.cards, &event.hand);
+    let chance_cards = scoring
+        .iter()
+        .filter(|&&idx| matches!(data.cards[idx].enhancement, LuckyCard | GlassCard))
+        .map(|&idx| Source::Card(idx))
+        .collect_vec();
+
+    let money = data.money;
+    let mut events = Vec::new();
+    score_hand_observed(&mut jokers, &mut data, &mut blind, &mut event, roller, &mut events);
+
+    let mut contributions = Vec::new();
+    let mut triggered = Vec::new();
+    for trace_event in events {
+        match trace_event {
+            TraceEvent::Contribution { source, chips, mult } => {
+                contributions.push(Contribution { source, chips, mult })
+            }
+            TraceEvent::ChancesHit(keys) => triggered = keys,
+            _ => {}
+        }
+    }
+
+    let chance_jokers = jokers
+        .iter()
+        .positions(|joker| {
+            matches!(
+                joker.joker_type,
+                Misprint | Bloodstone | BusinessCard | EightBall | SpaceJoker
+            )
+        })
+        .map(Source::Joker);
+    let chance = chance_cards.into_iter().chain(chance_jokers).collect();
+
+    let breakdown = ScoreBreakdown {
+        hand_type,
+        allowed: event.allowed,
+        scoring,
+        chips: blind.chips,
+        mult: blind.mult,
+        score: blind.score,
+        contributions,
+        chance,
+    };
+    let outcome =
+        Outcome { probability: 1., score: blind.score, money: data.money - money, triggered };
+
+    Some((breakdown, outcome))
+}
+
+/// Scores `hand` as if it was played right now, without touching the run or the blind.
+///
+/// Random effects roll on a copy of the rng, exactly as they would if the hand was played now.
+/// Returns `None` if there are no hands left to play.
+pub fn score_breakdown(
+    jokers: &[Joker],
+    data: &RunData,
+    blind: &Blind,
+    hand: &Hand,
+) -> Option<ScoreBreakdown> {
+    score_copy(jokers, data, blind, hand, &mut Seeded).map(|(breakdown, _)| breakdown)
+}
+
+/// Every possible result of playing `hand` right now, without looking at the rng.
+///
+/// Returns `None` if there are no hands left or the hand has more than [`MAX_OUTCOMES`] outcomes.
+pub fn score_distribution(
+    jokers: &[Joker],
+    data: &RunData,
+    blind: &Blind,
+    hand: &Hand,
+) -> Option<ScoreDistribution> {
+    score_distribution_capped(jokers, data, blind, hand, MAX_OUTCOMES)
+}
+
+/// [`score_distribution`], giving up beyond `max_outcomes` outcomes.
+pub fn score_distribution_capped(
+    jokers: &[Joker],
+    data: &RunData,
+    blind: &Blind,
+    hand: &Hand,
+    max_outcomes: usize,
+) -> Option<ScoreDistribution> {
+    let mut enumerator = Enumerator::new();
+    let mut outcomes = Vec::new();
+
+    loop {
+        let (_, outcome) = score_copy(jokers, data, blind, hand, &mut enumerator)?;
+        outcomes.push(Outcome { probability: enumerator.probability, ..outcome });
+
+        if outcomes.len() > max_outcomes {
+            return None;
+        }
+
+        if !enumerator.advance() {
+            return Some(ScoreDistribution { outcomes });
+        }
+    }
+}
+
+/// The mean score of `samples` plays of `hand`, for hands with too many outcomes to enumerate.
+///
+/// Every effect is rolled with a fixed-seed rng of its own, the run's rng isn't looked at.
+/// Returns `None` if there are no hands left to play.
+pub fn score_sampled_mean(
+    jokers: &[Joker],
+    data: &RunData,
+    blind: &Blind,
+    hand: &Hand,
+    samples: usize,
+) -> Option<f64> {
+    let mut sampler = Sampler::new();
+    let mut total = 0.;
+    for _ in 0..samples {
+        total += score_copy(jokers, data, blind, hand, &mut sampler)?.1.score;
+    }
+
+    Some(total / samples as f64)
+}
+
+/// The cards of `hand` that score, taking Splash into account.
+pub fn scoring_cards(jokers: &[Joker], cards: &[Card], hand: &Hand) -> Vec<usize> {
+    let splash = jokers.iter().any(|joker| !joker.debuffed && joker.joker_type == Splash);
+    hand.scoring(cards, splash)
+}
+
+/// Scores `event.hand` into `blind` in the same order as the game: played cards, jokers,
+/// consumables and finally the deck. Returns the callbacks the jokers want to run afterwards.
+pub fn score_hand(
+    jokers: &mut [Joker],
+    data: &mut RunData,
+    blind: &mut Blind,
+    event: &mut HandPlayedEventData,
+) -> Vec<Callback> {
+    score_hand_observed(jokers, data, blind, event, &mut Seeded, &mut NoObserver)
+}
+
+/// [`score_hand`], with random effects decided by `roller` and what every card, joker and
+/// consumable added reported to `observer`.
+pub fn score_hand_observed(
+    jokers: &mut [Joker],
+    data: &mut RunData,
+    blind: &mut Blind,
+    event: &mut HandPlayedEventData,
+    roller: &mut dyn Roller,
+    observer: &mut dyn Observer,
+) -> Vec<Callback> {
+    if !event.allowed {
+        return Vec::new();
+    }
+
+    let mut dice = Dice::new(roller, jokers);
+    let hand = event.hand.clone();
+    let cards = hand.card_slice().iter().map(|&idx| data.cards[idx].clone()).collect_vec();
+    let hand_type = ResolvedHand(cards.iter().collect()).hand_type();
+
+    let space_jokers =
+        jokers.iter().filter(|joker| !joker.debuffed && joker.joker_type == SpaceJoker);
+    for _ in space_jokers {
+        if dice.one_in(&mut data.rng, "space", 4.) {
+            blind.change_hand_level(data, hand_type, 1);
+        }
+    }
+
+    let scoring = scoring_cards(jokers, &data.cards, &hand);
+    for &card_idx in &scoring {
+        let card = data.cards[card_idx].clone();
+
+        // Red Seals retrigger the card and every joker reacting to it
+        for _ in 0..1 + (card.seal == Seal::Red) as usize {
+            let before = (blind.chips, blind.mult);
+            score_card(&card, data, blind, &mut dice);
+            contribution(observer, Source::Card(card_idx), blind, before);
+
+            let mut card_event = CardScoredEventData {
+                card: &card,
+                hand_played: event,
+                suit: MultiSuit::from(&card),
+                face_card: card.has_rank() && card.rank.is_face_card(),
+            };
+
+            for (joker_idx, joker) in
+                jokers.iter_mut().enumerate().filter(|(_, joker)| !joker.debuffed).sorted_by_key(
+                    |(_, joker)| joker.dispatcher_order.events[Event::CardScored as usize],
+                )
+            {
+                let before = (blind.chips, blind.mult);
+                joker.card_scored(data, blind, &mut card_event, &mut dice);
+
+                let source = Source::JokerOnCard { joker: joker_idx, card: card_idx };
+                contribution(observer, source, blind, before);
+            }
+        }
+    }
+
+    for &card_idx in &blind.held.clone() {
+        let card = &data.cards[card_idx];
+        if card.enhancement != SteelCard {
+            continue;
+        }
+
+        let before = (blind.chips, blind.mult);
+        for _ in 0..1 + (card.seal == Seal::Red) as usize {
+            blind.mult *= 1.5;
+        }
+        contribution(observer, Source::Card(card_idx), blind, before);
+    }
+
+    let event_usize = Event::Scored as usize;
+    let mut callbacks = Vec::new();
+
+    for (idx, joker) in jokers
+        .iter_mut()
+        .enumerate()
+        .filter(|(_, joker)| !joker.debuffed)
+        .sorted_by_key(|(_, joker)| joker.dispatcher_order.events[event_usize])
+    {
+        if !event.allowed {
+            return callbacks;
+        }
+
+        let before = (blind.chips, blind.mult);
+        joker.edition.scored(blind);
+        contribution(observer, Source::JokerEdition(idx), blind, before);
+
+        let before = (blind.chips, blind.mult);
+        if let Some(callback) = joker.scored(data, blind, event, &mut dice) {
+            callbacks.push(Box::new(misc::curry_mut(callback, idx)) as Callback);
+        }
+        contribution(observer, Source::Joker(idx), blind, before);
+
+        let before = (blind.chips, blind.mult);
+        joker.edition.scored_after_joker(blind);
+        contribution(observer, Source::JokerEdition(idx), blind, before);
+    }
+
+    for (idx, consumable) in data.consumables.iter().enumerate() {
+        let before = (blind.chips, blind.mult);
+        consumable.scored(data, blind, hand_type);
+        contribution(observer, Source::Consumable(idx), blind, before);
+    }
+
+    let before = (blind.chips, blind.mult);
+    data.deck_type.final_scoring_step(blind);
+    contribution(observer, Source::Deck, blind, before);
+
+    let score = blind.chips * blind.mult;
+    blind.score += score;
+    observer.observe(&TraceEvent::HandScored { chips: blind.chips, mult: blind.mult, score });
+
+    let mut shattered = Vec::new();
+    for &idx in &scoring {
+        if data.cards[idx].enhancement == GlassCard && dice.one_in(&mut data.rng, "glass", 4.) {
+            shattered.push(idx);
+        }
+    }
+
+    if !dice.triggered.is_empty() {
+        observer.observe(&TraceEvent::ChancesHit(dice.triggered));
+    }
+
+    if !shattered.is_empty() {
+        blind.destroy_cards(data, &shattered);
+        observer.observe(&TraceEvent::CardsDestroyed(shattered));
+    }
+
+    callbacks
+}
+
+/// A scored card's own effects, in the order the game's `eval_card` applies them.
+fn score_card(card: &Card, data: &mut RunData, blind: &mut Blind, dice: &mut Dice) {
+    let perma_bonus = card.chips.saturating_sub(Card::new(card.suit, card.rank).chips);
+    blind.chips += match card.enhancement {
+        StoneCard => 50. + perma_bonus as f64,
+        BonusCard => card.chips as f64 + 30.,
+        _ => card.chips as f64,
+    };
+
+    match card.enhancement {
+        MultCard => blind.mult += 4.,
+        LuckyCard if dice.one_in(&mut data.rng, "lucky_mult", 5.) => blind.mult += 20.,
+        _ => {}
+    }
+
+    if card.seal == Seal::Gold {
+        data.money += 3.;
+    }
+
+    if card.enhancement == LuckyCard && dice.one_in(&mut data.rng, "lucky_money", 15.) {
+        data.money += 20.;
+    }
+
+    if card.enhancement == GlassCard {
+        blind.mult *= 2.;
+    }
+
+    match card.edition {
+        Edition::Base => {}
+        Edition::Foil => blind.chips += 50.,
+        Edition::Holographic => blind.mult += 10.,
+        Edition::Polychrome => blind.mult *= 1.5,
+    }
+}
+
+fn contribution(observer: &mut dyn Observer, source: Source, blind: &Blind, before: (f64, f64)) {
+    let (chips, mult) = (blind.chips - before.0, blind.mult - before.1);
+
+    if chips != 0. || mult != 0. {
+        observer.observe(&TraceEvent::Contribution { source, chips, mult });
+    }
+}
@@ -0,0 +1,320 @@
+pub mod query;
+
+use crate::{
+    blind::{BlindType, BossBlindType},
+    boosters::BoosterPackType,
+    builders::run::RunCreator,
+    card::Card,
+    decks::DeckType,
+    forecast::{AnteForecast, Forecaster, ShopForecast},
+    joker::JokerType,
+    run::Run,
+    seeding::{SEED_ALPHABET, random_seed},
+    shop::ShopItem,
+    stake::Stake,
+    tags::Tag,
+    vouchers::Voucher,
+};
+use std::{
+    ops::{Bound, Not, RangeBounds},
+    sync::{
+        Arc,
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        mpsc::{Iter, Receiver, RecvError, TryRecvError, channel},
+    },
+    thread,
+};
+use typed_builder::TypedBuilder;
+
+/// Number of distinct 8 character seeds.
+pub const SEED_SPACE: u64 = (SEED_ALPHABET.len() as u64).pow(8);
+
+/// Maps `0..SEED_SPACE` onto every seed, in order of [`SEED_ALPHABET`].
+#[must_use]
+pub fn seed_from_index(mut idx: u64) -> String {
+    let base = SEED_ALPHABET.len() as u64;
+    let mut seed = [' '; 8];
+
+    for char in seed.iter_mut().rev() {
+        *char = SEED_ALPHABET[(idx % base) as usize];
+        idx /= base;
+    }
+
+    seed.iter().collect()
+}
+
+/// A freshly created run whose forecast is only computed as far as a filter asks for.
+pub struct Candidate {
+    run: Run,
+    forecaster: Forecaster,
+    antes: Vec<AnteForecast>,
+}
+
+impl Candidate {
+    #[must_use]
+    pub fn new(run: Run, rerolls: usize) -> Self {
+        let forecaster = Forecaster::new(&run, rerolls);
+        Self { run, forecaster, antes: Vec::new() }
+    }
+
+    pub fn seed(&self) -> &str {
+        &self.run.data.rng.seed
+    }
+
+    pub fn run(&self) -> &Run {
+        &self.run
+    }
+
+    pub fn cards(&self) -> &[Card] {
+        &self.run.data.cards
+    }
+
+    /// Forecasts every ante up to `ante`, returns `None` if the run starts after it.
+    pub fn ante(&mut self, ante: i32) -> Option<&AnteForecast> {
+        while self.antes.last().is_none_or(|last| last.ante < ante) {
+            self.antes.push(self.forecaster.next().unwrap());
+        }
+
+        self.antes.iter().find(|forecast| forecast.ante == ante)
+    }
+
+    /// All antes from the first one up to and including `ante`.
+    pub fn antes_up_to(&mut self, ante: i32) -> &[AnteForecast] {
+        self.ante(ante);
+        let end = self.antes.iter().take_while(|forecast| forecast.ante <= ante).count();
+        &self.antes[..end]
+    }
+}
+
+/// A predicate over a [`Candidate`], combinable with `and`, `or` and `!`.
+pub struct Filter(Box<dyn Fn(&mut Candidate) -> bool + Send + Sync>);
+
+impl Filter {
+    pub fn new(predicate: impl Fn(&mut Candidate) -> bool + Send + Sync + 'static) -> Self {
+        Self(Box::new(predicate))
+    }
+
+    pub fn matches(&self, candidate: &mut Candidate) -> bool {
+        (self.0)(candidate)
+    }
+
+    #[must_use]
+    pub fn and(self, other: Filter) -> Self {
+        Self::new(move |candidate| self.matches(candidate) && other.matches(candidate))
+    }
+
+    #[must_use]
+    pub fn or(self, other: Filter) -> Self {
+        Self::new(move |candidate| self.matches(candidate) || other.matches(candidate))
+    }
+
+    /// Any shop in `antes`, including its rerolls, sells an item matching `predicate`.
+    ///
+    /// Ranges without an end stop at the final ante.
+    pub fn shop_item(
+        antes: impl RangeBounds<i32> + Send + Sync + 'static,
+        predicate: impl Fn(&ShopItem) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        Self::shops(antes, move |shop| shop.inventories.iter().flatten().any(&predicate))
+    }
+
+    pub fn shop_joker(
+        antes: impl RangeBounds<i32> + Send + Sync + 'static,
+        joker_type: JokerType,
+    ) -> Self {
+        Self::shop_item(
+            antes,
+            move |item| matches!(item, ShopItem::Joker(joker) if joker.joker_type == joker_type),
+        )
+    }
+
+    pub fn pack(
+        antes: impl RangeBounds<i32> + Send + Sync + 'static,
+        pack: BoosterPackType,
+    ) -> Self {
+        Self::shops(antes, move |shop| shop.packs.contains(&pack))
+    }
+
+    fn shops(
+        antes: impl RangeBounds<i32> + Send + Sync + 'static,
+        predicate: impl Fn(&ShopForecast) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        let last = match antes.end_bound() {
+            Bound::Included(ante) => *ante,
+            Bound::Excluded(ante) => ante - 1,
+            Bound::Unbounded => 8,
+        };
+
+        Self::new(move |candidate| {
+            candidate
+                .antes_up_to(last)
+                .iter()
+                .filter(|forecast| antes.contains(&forecast.ante))
+                .flat_map(|forecast| &forecast.shops)
+                .any(&predicate)
+        })
+    }
+
+    /// The voucher offered in the shops of `ante`, before its Boss is beaten.
+    pub fn voucher(ante: i32, voucher: Voucher) -> Self {
+        Self::new(move |candidate| {
+            candidate.ante(ante).is_some_and(|forecast| {
+                forecast
+                    .shops
+                    .iter()
+                    .filter(|shop| !matches!(shop.after, BlindType::Boss(_)))
+                    .any(|shop| shop.vouchers.contains(&voucher))
+            })
+        })
+    }
+
+    /// One of the two skip tags of `ante`.
+    pub fn tag(ante: i32, tag: Tag) -> Self {
+        Self::new(move |candidate| {
+            candidate
+                .ante(ante)
+                .is_some_and(|forecast| forecast.tags.is_some_and(|tags| tags.contains(&tag)))
+        })
+    }
+
+    pub fn boss(ante: i32, boss: BossBlindType) -> Self {
+        Self::new(move |candidate| {
+            candidate.ante(ante).is_some_and(|forecast| forecast.boss == boss)
+        })
+    }
+
+    /// Mostly useful for Erratic decks.
+    pub fn cards(predicate: impl Fn(&[Card]) -> bool + Send + Sync + 'static) -> Self {
+        Self::new(move |candidate| predicate(candidate.cards()))
+    }
+}
+
+impl Not for Filter {
+    type Output = Filter;
+
+    fn not(self) -> Filter {
+        Self::new(move |candidate| !self.matches(candidate))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchMode {
+    /// Walks `count` seeds of the seed space in order, starting at `start`.
+    Exhaustive { start: u64, count: u64 },
+    /// Checks `count` seeds from `random_seed`, or keeps going until the handle is dropped.
+    Random { count: Option<u64> },
+}
+
+#[derive(TypedBuilder)]
+pub struct SeedSearch {
+    filter: Filter,
+
+    #[builder(default=SearchMode::Exhaustive { start: 0, count: SEED_SPACE })]
+    mode: SearchMode,
+
+    #[builder(default=DeckType::Red)]
+    deck: DeckType,
+
+    #[builder(default=Stake::White)]
+    stake: Stake,
+
+    /// Rerolls forecast for every shop.
+    #[builder(default = 0)]
+    rerolls: usize,
+
+    #[builder(default=thread::available_parallelism().map_or(1, usize::from))]
+    threads: usize,
+}
+
+/// The matches of a running [`SeedSearch`]. Dropping it stops the workers.
+pub struct SearchHandle {
+    receiver: Receiver<String>,
+    stop: Arc<AtomicBool>,
+}
+
+impl SearchHandle {
+    /// Waits for the next match, fails once the search is exhausted.
+    pub fn recv(&self) -> Result<String, RecvError> {
+        self.receiver.recv()
+    }
+
+    pub fn try_recv(&self) -> Result<String, TryRecvError> {
+        self.receiver.try_recv()
+    }
+
+    /// Every match until the search is exhausted.
+    pub fn iter(&self) -> Iter<'_, String> {
+        self.receiver.iter()
+    }
+
+    /// Tells the workers to stop after the seed they are checking.
+    pub fn stop(&self) {
+        self.stop.store(true, Ordering::Relaxed);
+    }
+}
+
+impl Drop for SearchHandle {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+/// Seeds handed to a worker at once.
+const CHUNK_SIZE: u64 = 256;
+
+impl SeedSearch {
+    /// Starts the worker threads and streams every matching seed.
+    ///
+    /// The search stops once it is exhausted or the handle is dropped.
+    #[must_use]
+    pub fn start(self) -> SearchHandle {
+        let Self { filter, mode, deck, stake, rerolls, threads } = self;
+        let (sender, receiver) = channel();
+
+        let filter = Arc::new(filter);
+        let next = Arc::new(AtomicU64::new(0));
+        let stop = Arc::new(AtomicBool::new(false));
+        let (start, count) = match mode {
+            SearchMode::Exhaustive { start, count } => {
+                (start, count.min(SEED_SPACE.saturating_sub(start)))
+            }
+            SearchMode::Random { count } => (0, count.unwrap_or(u64::MAX)),
+        };
+
+        for _ in 0..threads {
+            let (filter, next, sender) = (filter.clone(), next.clone(), sender.clone());
+            let stop = stop.clone();
+
+            thread::spawn(move || {
+                loop {
+                    let from = next.fetch_add(CHUNK_SIZE, Ordering::Relaxed);
+                    if from >= count {
+                        return;
+                    }
+
+                    for idx in from..(from + CHUNK_SIZE).min(count) {
+                        if stop.load(Ordering::Relaxed) {
+                            return;
+                        }
+
+                        let seed = match mode {
+                            SearchMode::Exhaustive { .. } => seed_from_index(start + idx),
+                            SearchMode::Random { .. } => random_seed(),
+                        };
+
+                        let run = RunCreator::builder().deck(deck).stake(stake).seed(seed).build();
+                        let mut candidate = Candidate::new(run.create(), rerolls);
+
+                        if filter.matches(&mut candidate)
+                            && sender.send(candidate.seed().to_string()).is_err()
+                        {
+                            return;
+                        }
+                    }
+                }
+            });
+        }
+
+        SearchHandle { receiver, stop }
+    }
+}
@@ -0,0 +1,105 @@
+//! A plain text format for [`Filter`]s.
+//!
+//! Clauses are separated by `;` or new lines and must all hold. Lines starting with `#` are
+//! ignored. Names are the enum variants, ignoring case.
+//!
+//! ```text
+//! ante<=2 shop contains Blueprint
+//! ante=3 pack !contains SpectralMega
+//! voucher ante1 = Telescope
+//! tag ante2 = NegativeTag
+//! boss ante8 != VioletVessel
+//! ```
+
+use super::Filter;
+use itertools::Itertools;
+use std::{
+    error::Error,
+    fmt::{Display, Formatter},
+    ops::RangeInclusive,
+    str::FromStr,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QueryError {
+    UnknownClause(String),
+    InvalidAnte(String),
+    UnknownName(String),
+}
+
+impl Display for QueryError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            QueryError::UnknownClause(clause) => write!(f, "unknown clause `{clause}`"),
+            QueryError::InvalidAnte(ante) => write!(f, "invalid ante `{ante}`"),
+            QueryError::UnknownName(name) => write!(f, "unknown name `{name}`"),
+        }
+    }
+}
+
+impl Error for QueryError {}
+
+impl FromStr for Filter {
+    type Err = QueryError;
+
+    fn from_str(query: &str) -> Result<Filter, QueryError> {
+        query
+            .split([';', '\n'])
+            .map(str::trim)
+            .filter(|clause| !clause.is_empty() && !clause.starts_with('#'))
+            .map(parse_clause)
+            .reduce(|acc, filter| Ok(acc?.and(filter?)))
+            .unwrap_or_else(|| Ok(Filter::new(|_| true)))
+    }
+}
+
+fn parse_clause(clause: &str) -> Result<Filter, QueryError> {
+    let tokens = clause.split_whitespace().collect_vec();
+
+    let (negated, filter) = match tokens[..] {
+        [antes, "shop", op @ ("contains" | "!contains"), joker] => {
+            (op == "!contains", Filter::shop_joker(parse_antes(antes)?, parse_name(joker)?))
+        }
+        [antes, "pack", op @ ("contains" | "!contains"), pack] => {
+            (op == "!contains", Filter::pack(parse_antes(antes)?, parse_name(pack)?))
+        }
+        [subject @ ("voucher" | "tag" | "boss"), ante, op @ ("=" | "!="), name] => {
+            let antes = parse_antes(ante)?;
+            if antes.start() != antes.end() {
+                return Err(QueryError::InvalidAnte(ante.to_string()));
+            }
+
+            let ante = *antes.start();
+            let filter = match subject {
+                "voucher" => Filter::voucher(ante, parse_name(name)?),
+                "tag" => Filter::tag(ante, parse_name(name)?),
+                _ => Filter::boss(ante, parse_name(name)?),
+            };
+
+            (op == "!=", filter)
+        }
+        _ => return Err(QueryError::UnknownClause(clause.to_string())),
+    };
+
+    Ok(if negated { !filter } else { filter })
+}
+
+/// Accepts `anteN`, `ante=N`, `ante<N` and `ante<=N`.
+fn parse_antes(token: &str) -> Result<RangeInclusive<i32>, QueryError> {
+    let invalid = || QueryError::InvalidAnte(token.to_string());
+    let spec = token.strip_prefix("ante").ok_or_else(invalid)?;
+    let number = |n: &str| n.parse::<i32>().map_err(|_| invalid());
+
+    if let Some(n) = spec.strip_prefix("<=") {
+        Ok(i32::MIN..=number(n)?)
+    } else if let Some(n) = spec.strip_prefix('<') {
+        Ok(i32::MIN..=number(n)? - 1)
+    } else {
+        let n = number(spec.strip_prefix('=').unwrap_or(spec))?;
+        Ok(n..=n)
+    }
+}
+
+fn parse_name<T: FromStr>(name: &str) -> Result<T, QueryError> {
+    name.parse().map_err(|_| QueryError::UnknownName(name.to_string()))
+}
@@ -1,5 +1,4 @@
-﻿use crate::misc::Also;
-use rand::{prelude::*, rng};
+﻿use rand::{prelude::*, rng};
 use std::{collections::HashMap, f64::consts::PI, iter::repeat_with};
 
 pub(crate) mod math {
@@ -38,6 +37,12 @@ pub(crate) mod math {
     }
 }
 
+/// Every character `random_seed` can produce.
+pub const SEED_ALPHABET: [char; 34] = [
+    '1', '2', '3', '4', '5', '6', '7', '8', '9', 'A', 'B', 'C', 'D', 'E', 'F', 'G', 'H', 'I', 'J',
+    'K', 'L', 'M', 'N', 'P', 'Q', 'R', 'S', 'T', 'U', 'V', 'W', 'X', 'Y', 'Z',
+];
+
 pub fn random_seed() -> String {
     let mut rng = rng();
 
@@ -89,6 +94,8 @@ pub fn random_idx<T>(list: &[T], seed: f64) -> usize {
     math::random_idx(list.len())
 }
 
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BalatroRng {
     pub seed: String,
     pub hashed_seed: f64,
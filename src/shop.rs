@@ -1,5 +1,15 @@
 ﻿use crate::{
-    boosters::BoosterPackType, card::Card, consumable::Consumable, joker::Joker, run::Run,
+    boosters::{BoosterPack, BoosterPackKind, BoosterPackType},
+    card::Card,
+    consumable::Consumable,
+    controller::ActionError,
+    joker::{
+        Joker,
+        JokerEdition::Negative,
+        JokerType::{Astronomer, ChaosTheClown},
+    },
+    run::Run,
+    tags::Tag::{CouponTag, D6Tag, VoucherTag},
     vouchers::Voucher,
 };
 use derive_more::From;
@@ -14,6 +24,7 @@ use strum::{EnumCount, EnumIter};
 
 #[repr(u8)]
 #[derive(Debug, Clone, PartialEq, EnumCount, From)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum ShopItem {
     Consumable(Consumable),
     Joker(Joker),
@@ -40,12 +51,18 @@ pub enum ShopItemType {
     SpectralCard,
 }
 
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Shop {
     pub weights: [f64; ShopItemType::COUNT],
     pub pack_weights: [f64; BoosterPackType::COUNT],
 
     pub price_multiplier: f64, // the game calls this `discount_percent`
+    pub base_reroll_price: f64,
+    pub temp_base_reroll_price: Option<f64>,
+    pub reroll_price_increase: f64,
     pub reroll_price: f64,
+    pub free_rerolls: u32,
     pub size: usize,
 
     pub edition_rate: f64,
@@ -54,6 +71,9 @@ pub struct Shop {
 
     pub first_shop_buffoon: bool,
     pub packs: [Option<BoosterPackType>; 2],
+
+    pub couponed_inventory: bool,
+    pub couponed_packs: bool,
 }
 
 impl Default for Shop {
@@ -62,13 +82,19 @@ impl Default for Shop {
             weights: [20., 4., 4., 0., 0.],
             pack_weights: [4., 2., 0.5, 4., 2., 0.5, 4., 2., 0.5, 1.2, 0.6, 0.15, 0.6, 0.3, 0.07],
             price_multiplier: 1.,
+            base_reroll_price: 5.,
+            temp_base_reroll_price: None,
+            reroll_price_increase: 0.,
             reroll_price: 5.,
+            free_rerolls: 0,
             size: 2,
             edition_rate: 1.,
             vouchers: Vec::new(),
             first_shop_buffoon: false,
             inventory: Vec::new(),
             packs: [None; 2],
+            couponed_inventory: false,
+            couponed_packs: false,
         }
     }
 }
@@ -87,24 +113,182 @@ impl Display for Shop {
     }
 }
 
+impl Shop {
+    pub fn cost(&self, base_cost: f64) -> f64 {
+        ((base_cost + 0.5) * self.price_multiplier).floor().max(1.)
+    }
+
+    pub fn sell_value(&self, base_cost: f64) -> f64 {
+        (self.cost(base_cost) / 2.).floor().max(1.)
+    }
+
+    pub fn update_reroll_price(&mut self) {
+        let base = self.temp_base_reroll_price.unwrap_or(self.base_reroll_price);
+
+        self.reroll_price = match self.free_rerolls {
+            0 => (base + self.reroll_price_increase).max(0.),
+            _ => 0.,
+        };
+    }
+}
+
+impl ShopItem {
+    pub fn base_cost(&self) -> f64 {
+        match self {
+            ShopItem::Consumable(consumable) => consumable.base_cost(),
+            ShopItem::Joker(joker) => joker.base_cost(),
+            ShopItem::PlayingCard(card) => 1. + card.edition.extra_cost(),
+        }
+    }
+}
+
 impl Run {
     pub fn enter_shop(&mut self, new_ante: bool) {
         if new_ante {
             self.data.shop.vouchers = vec![self.data.poll_next_voucher()];
         }
 
+        while self.data.take_tag(VoucherTag) {
+            let voucher = self.data.poll_tag_voucher();
+            self.data.shop.vouchers.push(voucher);
+        }
+
         self.regenerate_shop_inventory();
         self.data.shop.packs = array::from_fn(|_| Some(self.next_shop_booster_pack()));
+
+        let couponed = self.data.take_tag(CouponTag);
+        self.data.shop.couponed_inventory = couponed;
+        self.data.shop.couponed_packs = couponed;
+
+        let free_rerolls = self.jokers.iter().filter(|joker| joker.joker_type == ChaosTheClown);
+        self.data.shop.free_rerolls = free_rerolls.count() as u32;
+        self.data.shop.reroll_price_increase = 0.;
+        self.data.shop.temp_base_reroll_price = self.data.take_tag(D6Tag).then_some(0.);
+        self.data.shop.update_reroll_price();
     }
 
-    pub fn reroll(&mut self) {
-        self.regenerate_shop_inventory();
+    pub fn shop_item_cost(&self, item: &ShopItem) -> f64 {
+        match item {
+            _ if self.data.shop.couponed_inventory => 0.,
+            ShopItem::Consumable(Consumable::PlanetCard(_)) if self.has_joker(Astronomer) => 0.,
+            ShopItem::Joker(joker) if joker.stickers.rental => 1.,
+            item => self.data.shop.cost(item.base_cost()),
+        }
+    }
+
+    pub fn booster_pack_cost(&self, pack: BoosterPackType) -> f64 {
+        match pack {
+            _ if self.data.shop.couponed_packs => 0.,
+            pack if pack.kind() == BoosterPackKind::Celestial && self.has_joker(Astronomer) => 0.,
+            pack => self.data.shop.cost(pack.base_cost()),
+        }
+    }
+
+    pub fn buy_shop_item(&mut self, idx: usize) -> Result<ShopItem, ActionError> {
+        let item = self.data.shop.inventory.get(idx).ok_or(ActionError::OutOfRange(idx))?;
+        let cost = self.shop_item_cost(item);
+
+        let has_room = match item {
+            ShopItem::Joker(joker) => joker.edition == Negative || self.free_joker_slots() > 0,
+            ShopItem::Consumable(_) => self.data.consumables.len() < self.data.consumable_slots,
+            ShopItem::PlayingCard(_) => true,
+        };
+
+        if !has_room {
+            return Err(ActionError::NoRoom);
+        }
+
+        if !self.can_afford(cost) {
+            return Err(ActionError::NotEnoughMoney);
+        }
+
+        self.data.money -= cost;
+
+        let item = self.data.shop.inventory.remove(idx);
+        match item.clone() {
+            ShopItem::Consumable(consumable) => self.data.consumables.push(consumable),
+            ShopItem::Joker(joker) => self.jokers.push(joker),
+            ShopItem::PlayingCard(card) => self.data.cards.push(card),
+        }
+
+        Ok(item)
+    }
+
+    pub fn sell_joker(&mut self, idx: usize) -> Result<Joker, ActionError> {
+        if self.jokers.get(idx).ok_or(ActionError::OutOfRange(idx))?.stickers.eternal {
+            return Err(ActionError::Eternal);
+        }
+
+        let joker = self.jokers.remove(idx);
+        self.data.money += joker.sell_value as f64;
+
+        Ok(joker)
+    }
+
+    pub fn sell_consumable(&mut self, idx: usize) -> Result<Consumable, ActionError> {
+        let consumable = *self.data.consumables.get(idx).ok_or(ActionError::OutOfRange(idx))?;
+        self.data.money += self.data.consumable_sell_value(idx);
+        self.data.consumables.remove(idx);
+        if idx < self.data.consumable_extra_value.len() {
+            self.data.consumable_extra_value.remove(idx);
+        }
+
+        Ok(consumable)
+    }
+
+    pub fn buy_voucher(&mut self, idx: usize) -> Result<Voucher, ActionError> {
+        let voucher = *self.data.shop.vouchers.get(idx).ok_or(ActionError::OutOfRange(idx))?;
+        let cost = self.data.shop.cost(voucher.base_cost());
+
+        if !self.can_afford(cost) {
+            return Err(ActionError::NotEnoughMoney);
+        }
+
+        self.data.money -= cost;
+        self.data.shop.vouchers.remove(idx);
+        self.data.apply_voucher_effects(voucher);
+
+        Ok(voucher)
+    }
+
+    /// Buys the pack at `idx` of the packs still in the shop and opens it.
+    pub fn buy_booster_pack(&mut self, idx: usize) -> Result<BoosterPack, ActionError> {
+        let slot = (self.data.shop.packs.iter().positions(Option::is_some).nth(idx))
+            .ok_or(ActionError::OutOfRange(idx))?;
+        let pack_type = self.data.shop.packs[slot].unwrap();
+        let cost = self.booster_pack_cost(pack_type);
+
+        if !self.can_afford(cost) {
+            return Err(ActionError::NotEnoughMoney);
+        }
+
+        self.data.money -= cost;
+        self.data.shop.packs[slot] = None;
+
+        Ok(self.open_booster_pack(pack_type))
+    }
+
+    pub fn reroll(&mut self) -> Result<(), ActionError> {
+        if !self.can_afford(self.data.shop.reroll_price) {
+            return Err(ActionError::NotEnoughMoney);
+        }
 
         self.data.money -= self.data.shop.reroll_price;
-        self.data.shop.reroll_price += 1.;
+
+        let shop = &mut self.data.shop;
+        match shop.free_rerolls {
+            0 => shop.reroll_price_increase += 1.,
+            _ => shop.free_rerolls -= 1,
+        }
+
+        shop.update_reroll_price();
+        shop.couponed_inventory = false;
+
+        self.regenerate_shop_inventory();
+        Ok(())
     }
 
-    fn regenerate_shop_inventory(&mut self) {
+    pub(crate) fn regenerate_shop_inventory(&mut self) {
         let shop_size = self.data.shop.size;
         let inv = repeat_with(|| self.poll_next_shop_item()).take(shop_size).collect();
         self.data.shop.inventory = inv;
@@ -1,5 +1,8 @@
-﻿#[repr(u8)]
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Ord, PartialOrd)]
+﻿use num_derive::FromPrimitive;
+
+#[repr(u8)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Ord, PartialOrd, FromPrimitive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Stake {
     White,
     Red,
@@ -8,10 +8,12 @@
 use num_derive::FromPrimitive;
 use num_traits::FromPrimitive;
 use std::array;
-use strum::{EnumCount, EnumIter};
+use strum::{EnumCount, EnumIter, EnumString};
 
 #[repr(u8)]
-#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumCount, EnumIter, FromPrimitive)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumCount, EnumIter, FromPrimitive, EnumString)]
+#[strum(ascii_case_insensitive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Tag {
     UncommonTag,
     RareTag,
@@ -39,6 +41,39 @@ pub enum Tag {
     EconomyTag,
 }
 
+impl Tag {
+    #[inline]
+    #[must_use]
+    pub const fn game_name(&self) -> &'static str {
+        match self {
+            Tag::UncommonTag => "tag_uncommon",
+            Tag::RareTag => "tag_rare",
+            Tag::NegativeTag => "tag_negative",
+            Tag::FoilTag => "tag_foil",
+            Tag::HolographicTag => "tag_holo",
+            Tag::PolychromeTag => "tag_polychrome",
+            Tag::InvestmentTag => "tag_investment",
+            Tag::VoucherTag => "tag_voucher",
+            Tag::BossTag => "tag_boss",
+            Tag::StandardTag => "tag_standard",
+            Tag::CharmTag => "tag_charm",
+            Tag::MeteorTag => "tag_meteor",
+            Tag::BuffoonTag => "tag_buffoon",
+            Tag::HandyTag => "tag_handy",
+            Tag::GarbageTag => "tag_garbage",
+            Tag::EtherealTag => "tag_ethereal",
+            Tag::CouponTag => "tag_coupon",
+            Tag::DoubleTag => "tag_double",
+            Tag::JuggleTag => "tag_juggle",
+            Tag::D6Tag => "tag_d_six",
+            Tag::TopUpTag => "tag_top_up",
+            Tag::SpeedTag => "tag_skip",
+            Tag::OrbitalTag => "tag_orbital",
+            Tag::EconomyTag => "tag_economy",
+        }
+    }
+}
+
 const FIRST_ANTE_TAGS: [Tag; 15] = [
     BossTag,
     CharmTag,
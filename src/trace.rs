@@ -0,0 +1,91 @@
+use crate::{
+    blind::{BlindType, BossBlindType},
+    boosters::BoosterPackType,
+    controller::ActionError,
+    hands::HandType,
+    shop::ShopItem,
+    vouchers::Voucher,
+};
+
+/// Where a change to the chips or mult of a hand came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Source {
+    /// A played or held card, as an index into `RunData::cards`.
+    Card(usize),
+    /// A joker reacting to a played card. Both are indices.
+    JokerOnCard {
+        joker: usize,
+        card: usize,
+    },
+    /// A joker, as an index into `Run::jokers`.
+    Joker(usize),
+    JokerEdition(usize),
+    /// An index into `RunData::consumables`.
+    Consumable(usize),
+    Deck,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TraceEvent {
+    BlindEntered {
+        blind_type: BlindType,
+        requirement: f64,
+    },
+    /// Indices into `RunData::cards`.
+    CardsDrawn(Vec<usize>),
+    HandPlayed {
+        hand_type: HandType,
+        cards: Vec<usize>,
+        scoring: Vec<usize>,
+    },
+    /// The chips and mult added by `source`. Multiplicative mult shows up as its difference.
+    Contribution {
+        source: Source,
+        chips: f64,
+        mult: f64,
+    },
+    HandScored {
+        chips: f64,
+        mult: f64,
+        score: f64,
+    },
+    /// Keys of the random effects that hit while scoring, in order.
+    ChancesHit(Vec<&'static str>),
+    /// Glass Cards that shattered, as indices into `RunData::cards` before they were removed.
+    CardsDestroyed(Vec<usize>),
+    MoneyChanged {
+        from: f64,
+        to: f64,
+    },
+    ShopGenerated {
+        inventory: Vec<ShopItem>,
+        packs: Vec<BoosterPackType>,
+        vouchers: Vec<Voucher>,
+    },
+    BossChosen(BossBlindType),
+    ActionRejected(ActionError),
+}
+
+/// Receives everything that happens during [`Run::simulate_observed`](crate::run::Run::simulate_observed).
+pub trait Observer {
+    fn observe(&mut self, event: &TraceEvent);
+}
+
+/// Ignores every event.
+pub struct NoObserver;
+
+impl Observer for NoObserver {
+    fn observe(&mut self, _: &TraceEvent) {}
+}
+
+impl Observer for Vec<TraceEvent> {
+    fn observe(&mut self, event: &TraceEvent) {
+        self.push(event.clone());
+    }
+}
+
+impl<O: Observer + ?Sized> Observer for &mut O {
+    fn observe(&mut self, event: &TraceEvent) {
+        (**self).observe(event);
+    }
+}
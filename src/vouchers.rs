@@ -1,8 +1,13 @@
 ﻿use num_derive::FromPrimitive;
-use strum::{Display, EnumCount, EnumIter};
+use num_traits::FromPrimitive;
+use strum::{Display, EnumCount, EnumIter, EnumString};
 
 #[repr(u8)]
-#[derive(Debug, Clone, Copy, PartialEq, Eq, EnumCount, EnumIter, FromPrimitive, Display)]
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, EnumCount, EnumIter, FromPrimitive, Display, EnumString,
+)]
+#[strum(ascii_case_insensitive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Voucher {
     Overstock,
     OverstockPlus,
@@ -44,4 +49,59 @@ impl Voucher {
     pub fn requirements_fulfilled(self, voucher_list: &[bool; Voucher::COUNT]) -> bool {
         (self as usize).is_multiple_of(2) || voucher_list[self as usize - 1]
     }
+
+    /// The tier 2 voucher that buying `self` makes available.
+    #[inline]
+    #[must_use]
+    pub fn upgrade(self) -> Option<Voucher> {
+        match (self as usize).is_multiple_of(2) {
+            true => Voucher::from_usize(self as usize + 1),
+            false => None,
+        }
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn base_cost(self) -> f64 {
+        10.
+    }
+
+    #[inline]
+    #[must_use]
+    pub const fn game_name(&self) -> &'static str {
+        match self {
+            Voucher::Overstock => "v_overstock_norm",
+            Voucher::OverstockPlus => "v_overstock_plus",
+            Voucher::ClearanceSale => "v_clearance_sale",
+            Voucher::Liquidation => "v_liquidation",
+            Voucher::Hone => "v_hone",
+            Voucher::GlowUp => "v_glow_up",
+            Voucher::RerollSurplus => "v_reroll_surplus",
+            Voucher::RerollGlut => "v_reroll_glut",
+            Voucher::CrystalBall => "v_crystal_ball",
+            Voucher::OmenGlobe => "v_omen_globe",
+            Voucher::Telescope => "v_telescope",
+            Voucher::Observatory => "v_observatory",
+            Voucher::Grabber => "v_grabber",
+            Voucher::NachoTong => "v_nacho_tong",
+            Voucher::Wasteful => "v_wasteful",
+            Voucher::Recyclomancy => "v_recyclomancy",
+            Voucher::TarotMerchant => "v_tarot_merchant",
+            Voucher::TarotTycoon => "v_tarot_tycoon",
+            Voucher::PlanetMerchant => "v_planet_merchant",
+            Voucher::PlanetTycoon => "v_planet_tycoon",
+            Voucher::SeedMoney => "v_seed_money",
+            Voucher::MoneyTree => "v_money_tree",
+            Voucher::Blank => "v_blank",
+            Voucher::Antimatter => "v_antimatter",
+            Voucher::MagicTrick => "v_magic_trick",
+            Voucher::Illusion => "v_illusion",
+            Voucher::Hieroglyph => "v_hieroglyph",
+            Voucher::Petroglyph => "v_petroglyph",
+            Voucher::DirectorsCut => "v_directors_cut",
+            Voucher::Retcon => "v_retcon",
+            Voucher::PaintBrush => "v_paint_brush",
+            Voucher::Palette => "v_palette",
+        }
+    }
 }
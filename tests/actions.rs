@@ -0,0 +1,127 @@
+use balatro_logic::{
+    blind::{
+        Blind,
+        BlindType::Boss,
+        BossBlindData,
+        BossBlindType::{TheEye, TheMouth},
+    },
+    builders::run::RunCreator,
+    controller::{ActionError, BlindAction, Controller, SimulationResult},
+    event_list::HandPlayedEventData,
+    game_state::GameState,
+    hands::HandType::{HighCard, Pair},
+    observation::{BlindView, Observation},
+    trace::TraceEvent,
+};
+
+#[test]
+fn blind_actions() {
+    let run = RunCreator::builder().seed("AAAAAAAA".to_string()).build().create();
+    let GameState::Blind(blind) = &run.game_state else { unreachable!() };
+    let mut blind = Blind { discards: 0, ..blind.clone() };
+
+    assert_eq!(blind.select(8), Err(ActionError::OutOfRange(8)));
+    assert_eq!(blind.can_play(&run.data), Err(ActionError::NothingSelected));
+    for idx in 0..5 {
+        blind.select(idx).unwrap();
+    }
+    assert_eq!(blind.select(3), Err(ActionError::Deselection(3)));
+    assert_eq!(blind.select(5), Err(ActionError::SelectionFull));
+    assert_eq!(blind.discard(), Err(ActionError::NoDiscards));
+
+    blind.hands = 0;
+    assert_eq!(blind.can_play(&run.data), Err(ActionError::NoHands));
+    assert_eq!(blind.prepare_play(&run.data), Err(ActionError::NoHands));
+}
+
+#[test]
+fn bosses_reject_hands() {
+    let run = RunCreator::builder().seed("AAAAAAAA".to_string()).build().create();
+    let pair = |blind: &mut Blind| {
+        blind.held = vec![0, 13, 1];
+        blind.selected = Default::default();
+        blind.select(0).unwrap();
+        blind.select(1).unwrap();
+    };
+
+    let mut mouth = Blind {
+        hands: 2,
+        blind_type: Boss(TheMouth),
+        blind_data: Some(BossBlindData::TheMouth { allowed_hand: Some(HighCard) }),
+        ..Default::default()
+    };
+    pair(&mut mouth);
+    assert_eq!(mouth.can_play(&run.data), Err(ActionError::HandNotAllowed(Pair)));
+    assert_eq!(mouth.prepare_play(&run.data), Err(ActionError::HandNotAllowed(Pair)));
+    assert_eq!(mouth.held, [0, 13, 1]);
+
+    let mut eye = Blind {
+        hands: 2,
+        blind_type: Boss(TheEye),
+        blind_data: Boss(TheEye).default_data(),
+        ..Default::default()
+    };
+    pair(&mut eye);
+    assert_eq!(eye.can_play(&run.data), Ok(()));
+    let mut event =
+        HandPlayedEventData { hand: eye.prepare_play(&run.data).unwrap(), allowed: true };
+    eye.hand_played(&mut run.data.clone(), &mut event);
+    pair(&mut eye);
+    assert_eq!(eye.can_play(&run.data), Err(ActionError::HandNotAllowed(Pair)));
+}
+
+#[test]
+fn invalid_actions_are_reported() {
+    #[derive(Default)]
+    struct Clumsy {
+        turns: usize,
+        rejected: Vec<ActionError>,
+        views: Vec<BlindView>,
+    }
+
+    impl Controller for Clumsy {
+        fn blind(&mut self, _: &Observation, blind: &BlindView) -> Vec<BlindAction> {
+            self.turns += 1;
+            self.views.push(blind.clone());
+
+            match self.turns {
+                1 => vec![
+                    BlindAction::SelectCard(20),
+                    BlindAction::SelectCard(0),
+                    BlindAction::SelectCard(0),
+                    BlindAction::Discard,
+                    BlindAction::Discard,
+                ],
+                _ => vec![BlindAction::Play, BlindAction::Abort],
+            }
+        }
+
+        fn rejected(&mut self, error: ActionError) {
+            self.rejected.push(error);
+        }
+    }
+
+    let run = RunCreator::builder().seed("AAAAAAAA".to_string()).build().create();
+    let mut clumsy = Clumsy::default();
+    let mut events = Vec::new();
+    assert_eq!(run.simulate_observed(&mut clumsy, &mut events), SimulationResult::Aborted);
+
+    let expected = [
+        ActionError::OutOfRange(20),
+        ActionError::Deselection(0),
+        ActionError::NothingSelected,
+        ActionError::NothingSelected,
+    ];
+    assert_eq!(clumsy.rejected, expected);
+    // The discard went through and was refilled
+    let [first, second] = &clumsy.views[..] else { unreachable!() };
+    assert_eq!(second.discards, first.discards - 1);
+    assert_eq!(second.held.len(), 8);
+    assert_eq!(second.held[..7], first.held[1..]);
+
+    let rejected = events.iter().filter_map(|event| match event {
+        TraceEvent::ActionRejected(error) => Some(*error),
+        _ => None,
+    });
+    assert!(rejected.eq(expected));
+}
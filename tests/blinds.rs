@@ -1,14 +1,56 @@
 ﻿use balatro_logic::{
-    blind::BossBlindType::{
-        TheFish, TheFlint, TheHouse, TheManacle, TheTooth, TheWall, TheWindow, VerdantLeaf,
+    blind::{
+        Blind,
+        BlindType::Boss,
+        BossBlindData,
+        BossBlindType::{
+            self, TheEye, TheFish, TheFlint, TheHouse, TheManacle, TheMark, TheTooth, TheWall,
+            TheWheel, TheWindow, VerdantLeaf,
+        },
+        PLAY_OUTCOMES,
     },
     builders::run::RunCreator,
+    card::{
+        Enhancement::LuckyCard,
+        Rank::{Jack, King, Queen},
+    },
+    event::DispatcherOrder,
+    event_list::HandPlayedEventData,
+    hands::{
+        Hand,
+        HandType::{Flush, FullHouse, Pair},
+    },
+    joker::{Joker, JokerEdition, JokerInternalState, JokerType, Stickers},
+    run::Run,
+    scoring::{score_distribution, score_sampled_mean},
+    seeding::BalatroRng,
     tags::Tag::{
         BossTag, CharmTag, CouponTag, D6Tag, EconomyTag, EtherealTag, GarbageTag, JuggleTag,
         NegativeTag, RareTag, SpeedTag, StandardTag, UncommonTag,
     },
 };
 
+fn joker(joker_type: JokerType) -> Joker {
+    Joker {
+        data: JokerInternalState::None,
+        joker_type,
+        edition: JokerEdition::Base,
+        stickers: Stickers::default(),
+        sell_value: 0,
+        debuffed: false,
+        dispatcher_order: DispatcherOrder::default(),
+    }
+}
+
+/// A `boss` blind with the whole deck left to draw.
+fn deal(run: &mut Run, boss: BossBlindType) -> Blind {
+    let cards = (0..run.data.cards.len()).collect();
+    let mut blind =
+        Blind { blind_type: Boss(boss), cards, hands: 2, discards: 1, ..Default::default() };
+    blind.draw(&run.jokers, &mut run.data);
+    blind
+}
+
 #[test]
 fn boss_blinds() {
     let mut run = RunCreator::builder().seed("AAAAAAAA".to_string()).build().create();
@@ -48,3 +90,121 @@ fn skip_tags() {
         run.data.ante += 1;
     }
 }
+
+#[test]
+fn best_plays() {
+    let run = RunCreator::builder().seed("AAAAAAAA".to_string()).build().create();
+    // 2♣ 2♦ 2♥ 3♣ 3♠ Q♠
+    let mut blind = Blind { hands: 1, held: vec![0, 13, 26, 1, 40, 50], ..Default::default() };
+
+    let plays = blind.best_plays(&run.jokers, &run.data);
+    assert_eq!(plays.len(), 6 + 15 + 20 + 15 + 6);
+    assert_eq!(plays[0].held, [0, 1, 2, 3, 4]);
+    assert_eq!(plays[0].hand_type, FullHouse);
+    // (40 + 2 + 2 + 2 + 3 + 3) chips * 4 mult
+    assert_eq!(plays[0].score, 52. * 4.);
+    assert!(plays.windows(2).all(|pair| pair[0].score >= pair[1].score));
+    assert_eq!(blind.discard_candidates(&run.data, &plays), [5]);
+
+    blind.blind_type = Boss(TheEye);
+    blind.blind_data = Some(BossBlindData::TheEye {
+        was_already_played: [
+            false, false, false, false, false, false, true, false, false, false, false, false,
+        ],
+    });
+    let plays = blind.best_plays(&run.jokers, &run.data);
+    assert_ne!(plays[0].hand_type, FullHouse);
+    assert_eq!(plays.last().unwrap().hand_type, FullHouse);
+    assert_eq!(plays.last().unwrap().score, 0.);
+}
+
+#[test]
+fn kickers_rank_below_the_pair() {
+    let run = RunCreator::builder().seed("AAAAAAAA".to_string()).build().create();
+    // 2♣ 2♦ 5♣ 9♣ K♣
+    let blind = Blind { hands: 1, held: vec![0, 13, 3, 7, 10], ..Default::default() };
+
+    let plays = blind.best_plays(&run.jokers, &run.data);
+    assert_eq!(plays[0].held, [0, 1]);
+    assert_eq!(plays[0].hand_type, Pair);
+    // (10 + 2 + 2) chips * 2 mult, the same as with any kickers
+    assert_eq!(plays[0].score, 28.);
+    assert_eq!(plays[1].score, 28.);
+    assert_eq!(plays[1].held.len(), 3);
+}
+
+#[test]
+fn plays_with_many_outcomes_use_a_sampled_mean() {
+    let mut run = RunCreator::builder().seed("AAAAAAAA".to_string()).build().create();
+    // 2♣ 3♣ 4♣ 5♣ 9♣, all Lucky Cards with 2 rolls each
+    let held = vec![0, 1, 2, 3, 7];
+    for &idx in &held {
+        run.data.cards[idx].enhancement = LuckyCard;
+    }
+    let blind = Blind { hands: 1, held, ..Default::default() };
+    let hand = Hand { cards: [0, 1, 2, 3, 7], len: 5 };
+
+    let flush = |run: &Run| {
+        let plays = blind.best_plays(&run.jokers, &run.data);
+        plays.iter().find(|play| play.hand_type == Flush).unwrap().score
+    };
+    let sampled = score_sampled_mean(&run.jokers, &run.data, &blind, &hand, PLAY_OUTCOMES);
+    assert_eq!(Some(flush(&run)), sampled);
+
+    let mean = score_distribution(&run.jokers, &run.data, &blind, &hand).unwrap().mean();
+    assert!((flush(&run) - mean).abs() < mean * 0.1);
+
+    // The samples don't depend on the run's rng
+    run.data.rng = BalatroRng::new("BBBBBBBB".to_string());
+    assert_eq!(Some(flush(&run)), sampled);
+}
+
+#[test]
+fn face_down_draws() {
+    let mut run = RunCreator::builder().seed("AAAAAAAA".to_string()).build().create();
+
+    // The House deals the first hand face down, later draws face up
+    let mut house = deal(&mut run, TheHouse);
+    assert_eq!(house.face_down, house.held);
+    house.select(0).unwrap();
+    house.prepare_play(&run.data).unwrap();
+    house.draw(&run.jokers, &mut run.data);
+    assert_eq!(house.face_down, house.held[..7]);
+
+    // The Fish deals face down after a hand is played, not after a discard
+    let mut fish = deal(&mut run, TheFish);
+    assert!(fish.face_down.is_empty());
+    fish.select(0).unwrap();
+    fish.discard().unwrap();
+    fish.draw(&run.jokers, &mut run.data);
+    assert!(fish.face_down.is_empty());
+    fish.select(0).unwrap();
+    let mut event =
+        HandPlayedEventData { hand: fish.prepare_play(&run.data).unwrap(), allowed: true };
+    fish.hand_played(&mut run.data, &mut event);
+    fish.draw(&run.jokers, &mut run.data);
+    assert_eq!(fish.face_down, fish.held[7..]);
+
+    // The Mark deals face cards face down, every card with a working Pareidolia
+    let face = |blind: &Blind, run: &Run| {
+        blind
+            .held
+            .iter()
+            .copied()
+            .filter(|&idx| matches!(run.data.cards[idx].rank, Jack | Queen | King))
+            .collect::<Vec<_>>()
+    };
+    let mark = deal(&mut run, TheMark);
+    assert_eq!(mark.face_down, face(&mark, &run));
+    run.jokers.push(Joker { debuffed: true, ..joker(JokerType::Pareidolia) });
+    let mark = deal(&mut run, TheMark);
+    assert_eq!(mark.face_down, face(&mark, &run));
+    run.jokers[0].debuffed = false;
+    let mark = deal(&mut run, TheMark);
+    assert_eq!(mark.face_down, mark.held);
+
+    // The Wheel's 1 in 7 is certain with three Oops! All 6s
+    run.jokers = vec![joker(JokerType::OopsAll6s); 3];
+    let wheel = deal(&mut run, TheWheel);
+    assert_eq!(wheel.face_down, wheel.held);
+}
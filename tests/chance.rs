@@ -0,0 +1,90 @@
+use balatro_logic::{
+    blind::Blind,
+    builders::run::RunCreator,
+    card::Enhancement::{GlassCard, LuckyCard},
+    chance::odds,
+    event::DispatcherOrder,
+    hands::Hand,
+    joker::{Joker, JokerEdition, JokerInternalState, JokerType, Stickers},
+    run::Run,
+    scoring::score_distribution,
+};
+
+fn joker(joker_type: JokerType) -> Joker {
+    Joker {
+        data: JokerInternalState::None,
+        joker_type,
+        edition: JokerEdition::Base,
+        stickers: Stickers::default(),
+        sell_value: 0,
+        debuffed: false,
+        dispatcher_order: DispatcherOrder::default(),
+    }
+}
+
+fn run() -> Run {
+    RunCreator::builder().seed("AAAAAAAA".to_string()).build().create()
+}
+
+fn close(a: f64, b: f64) -> bool {
+    (a - b).abs() < 1e-9
+}
+
+#[test]
+fn lucky_card() {
+    let mut run = run();
+    run.data.cards[0].enhancement = LuckyCard;
+    let blind = Blind { hands: 1, held: vec![0], requirement: 100., ..Default::default() };
+    let hand = Hand { cards: [0; 5], len: 1 };
+    let rng = run.data.rng.clone();
+
+    // A lone 2 scores 7 chips, the Lucky Card adds 20 mult 1 in 5 times
+    let distribution = score_distribution(&run.jokers, &run.data, &blind, &hand).unwrap();
+    assert_eq!(distribution.outcomes.len(), 4);
+    assert!(close(distribution.outcomes.iter().map(|outcome| outcome.probability).sum(), 1.));
+    assert!(close(distribution.mean(), 7. * (1. + 20. / 5.)));
+    assert!(close(distribution.probability_of("lucky_mult"), 1. / 5.));
+    assert!(close(distribution.probability_of("lucky_money"), 1. / 15.));
+    assert!(close(distribution.probability_to_clear(&blind), 1. / 5.));
+    assert_eq!(run.data.rng.pseudorandom_state, rng.pseudorandom_state);
+
+    run.jokers.push(joker(JokerType::OopsAll6s));
+    assert!(close(odds(&run.jokers, 5.), 2. / 5.));
+    let distribution = score_distribution(&run.jokers, &run.data, &blind, &hand).unwrap();
+    assert!(close(distribution.probability_of("lucky_mult"), 2. / 5.));
+    assert!(close(distribution.probability_of("lucky_money"), 2. / 15.));
+    let money = distribution.outcomes.iter().map(|outcome| outcome.probability * outcome.money);
+    assert!(close(money.sum(), 20. * 2. / 15.));
+}
+
+#[test]
+fn glass_card() {
+    let mut run = run();
+    run.data.cards[0].enhancement = GlassCard;
+    let blind = Blind { hands: 1, held: vec![0], ..Default::default() };
+    let hand = Hand { cards: [0; 5], len: 1 };
+
+    let distribution = score_distribution(&run.jokers, &run.data, &blind, &hand).unwrap();
+    assert_eq!(distribution.outcomes.len(), 2);
+    assert!(close(distribution.mean(), 7. * 2.));
+    assert!(close(distribution.probability_of("glass"), 1. / 4.));
+
+    run.jokers.push(joker(JokerType::OopsAll6s));
+    let distribution = score_distribution(&run.jokers, &run.data, &blind, &hand).unwrap();
+    assert!(close(distribution.probability_of("glass"), 2. / 4.));
+}
+
+#[test]
+fn misprint() {
+    let mut run = run();
+    run.jokers.push(joker(JokerType::Misprint));
+    let blind = Blind { hands: 1, held: vec![0, 13], ..Default::default() };
+    let hand = Hand { cards: [0, 13, 0, 0, 0], len: 2 };
+
+    // A pair of 2s scores 14 chips and 2 mult, plus 0 to 23 mult
+    let distribution = score_distribution(&run.jokers, &run.data, &blind, &hand).unwrap();
+    assert_eq!(distribution.outcomes.len(), 24);
+    assert!(close(distribution.mean(), 14. * (2. + 11.5)));
+    assert!(close(distribution.probability_at_least(14. * 24.), 2. / 24.));
+    assert!(distribution.variance() > 0.);
+}
@@ -0,0 +1,56 @@
+use balatro_logic::{
+    blind::{
+        Blind,
+        BlindType::{Big, Boss, Small},
+        BossBlindType::TheHook,
+    },
+    builders::run::RunCreator,
+    decks::DeckType,
+    game_state::GameState,
+    run::Run,
+    tags::Tag::DoubleTag,
+};
+
+fn run_with(deck: DeckType) -> Run {
+    RunCreator::builder().deck(deck).seed("AAAAAAAA".to_string()).build().create()
+}
+
+#[test]
+fn green_deck() {
+    let blind = Blind { hands: 2, discards: 3, ..Default::default() };
+
+    let mut red = run_with(DeckType::Red);
+    red.data.money = 20.;
+    assert_eq!(red.data.end_round(&blind), 3. + 2. + 4.);
+
+    let mut green = run_with(DeckType::Green);
+    green.data.money = 20.;
+    assert_eq!(green.data.end_round(&blind), 3. + 2. * 2. + 3.);
+    assert_eq!(green.data.blind_on_deck, Big);
+}
+
+#[test]
+fn plasma_deck() {
+    let run = run_with(DeckType::Plasma);
+
+    let GameState::Blind(blind) = &run.game_state else { unreachable!() };
+    assert_eq!(blind.requirement, 600.);
+
+    let mut blind = Blind { chips: 40., mult: 7., ..Default::default() };
+    DeckType::Plasma.final_scoring_step(&mut blind);
+    assert_eq!((blind.chips, blind.mult), (23., 23.));
+}
+
+#[test]
+fn anaglyph_deck() {
+    let mut run = run_with(DeckType::Anaglyph);
+
+    run.data.end_round(&Blind { blind_type: Small, ..Default::default() });
+    run.data.end_round(&Blind { blind_type: Big, ..Default::default() });
+    assert!(run.data.tags.is_empty());
+
+    run.data.end_round(&Blind { blind_type: Boss(TheHook), ..Default::default() });
+    assert_eq!(run.data.tags, [DoubleTag]);
+    assert_eq!(run.data.ante, 2);
+    assert_eq!(run.data.blind_on_deck, Small);
+}
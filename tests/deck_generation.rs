@@ -1,5 +1,4 @@
 ﻿use balatro_logic::{
-    blind::Blind,
     builders::run::RunCreator,
     card::{
         Card,
@@ -8,7 +7,7 @@
     },
     controller::{BlindAction, Controller, SimulationResult},
     decks::DeckType,
-    run::RunData,
+    observation::{BlindView, Observation},
     seeding::BalatroRng,
     stake::Stake,
 };
@@ -27,18 +26,18 @@ fn initial_draw() {
     struct Simulation;
 
     impl Controller for Simulation {
-        fn blind(&mut self, blind: &mut Blind, data: &mut RunData) -> Vec<BlindAction> {
+        fn blind(&mut self, _: &Observation, blind: &BlindView) -> Vec<BlindAction> {
             assert_eq!(
-                data.resolve(&blind.held).iter().sorted().collect_vec(),
+                blind.held.iter().flatten().sorted().collect_vec(),
                 [
-                    &Card::new(Diamond, Queen),
-                    &Card::new(Club, Queen),
-                    &Card::new(Heart, Rank10),
-                    &Card::new(Spade, Rank8),
-                    &Card::new(Diamond, Rank7),
-                    &Card::new(Diamond, Rank2),
-                    &Card::new(Heart, Rank2),
-                    &Card::new(Spade, Rank2)
+                    Card::new(Diamond, Queen),
+                    Card::new(Club, Queen),
+                    Card::new(Heart, Rank10),
+                    Card::new(Spade, Rank8),
+                    Card::new(Diamond, Rank7),
+                    Card::new(Diamond, Rank2),
+                    Card::new(Heart, Rank2),
+                    Card::new(Spade, Rank2)
                 ]
                 .iter()
                 .sorted()
@@ -0,0 +1,120 @@
+use balatro_logic::{
+    blind::Blind,
+    builders::run::RunCreator,
+    card::{
+        Card,
+        Enhancement::{StoneCard, WildCard},
+        Rank::{self, *},
+        Suit::{self, *},
+    },
+    draw::Outs,
+    game_state::GameState,
+    run::RunData,
+};
+
+fn close(a: f64, b: f64) -> bool {
+    (a - b).abs() < 1e-9
+}
+
+fn binomial(n: u32, k: u32) -> f64 {
+    (0..k).fold(1., |ways, i| ways * (n - i) as f64 / (i + 1) as f64)
+}
+
+/// A blind holding `held`, with the rest of the standard deck left to draw.
+fn holding(data: &RunData, held: &[(Suit, Rank)]) -> Blind {
+    let held = held
+        .iter()
+        .map(|&(suit, rank)| {
+            data.cards.iter().position(|card| card.suit == suit && card.rank == rank).unwrap()
+        })
+        .collect::<Vec<_>>();
+    let cards = (0..data.cards.len()).filter(|idx| !held.contains(idx)).collect();
+
+    Blind { cards, held, hands: 1, ..Default::default() }
+}
+
+#[test]
+fn peek_matches_draw() {
+    let mut run = RunCreator::builder().seed("AAAAAAAA".to_string()).build().create();
+    let GameState::Blind(blind) = &run.game_state else { unreachable!() };
+
+    let mut blind = blind.clone();
+    let upcoming = blind.peek(3);
+    assert_eq!(upcoming.len(), 3);
+
+    blind.held.truncate(blind.held.len() - 3);
+    blind.draw(&run.jokers, &mut run.data);
+    assert_eq!(blind.held[blind.held.len() - 3..], upcoming);
+}
+
+#[test]
+fn outs_ignore_the_order() {
+    let run = RunCreator::builder().seed("AAAAAAAA".to_string()).build().create();
+    let GameState::Blind(blind) = &run.game_state else { unreachable!() };
+
+    let mut reversed = blind.clone();
+    reversed.cards.reverse();
+    assert_eq!(Outs::new(blind, &run.data), Outs::new(&reversed, &run.data));
+}
+
+#[test]
+fn draw_odds() {
+    let run = RunCreator::builder().seed("AAAAAAAA".to_string()).build().create();
+    let data = &run.data;
+    let held = |blind: &Blind| blind.held.iter().map(|&idx| &data.cards[idx]).collect::<Vec<_>>();
+
+    let outs = Outs::new(&holding(data, &[]), data);
+    let hearts = outs.at_least(5, 1, |card: &Card| card.suit == Heart);
+    assert!(close(hearts, 1. - binomial(39, 5) / binomial(52, 5)));
+
+    let blind = holding(data, &[(Heart, Rank2), (Heart, Rank7), (Heart, Jack), (Heart, King)]);
+    let outs = Outs::new(&blind, data);
+    assert!(close(outs.flush(&held(&blind), 1), 9. / 48.));
+    assert!(close(outs.flush(&held(&blind), 0), 0.));
+
+    let blind = holding(data, &[(Club, Rank2), (Diamond, Rank2)]);
+    let outs = Outs::new(&blind, data);
+    assert!(close(outs.of_a_kind(&held(&blind), 3, 1), 2. / 50.));
+    assert!(close(outs.of_a_kind(&held(&blind), 2, 1), 1.));
+
+    let blind = holding(data, &[(Club, Rank5), (Heart, Rank6), (Spade, Rank7), (Club, Rank8)]);
+    let outs = Outs::new(&blind, data);
+    assert!(close(outs.straight(&held(&blind), 1), 8. / 48.));
+
+    let blind = holding(data, &[(Club, Ace), (Heart, Rank2), (Spade, Rank3), (Club, Rank4)]);
+    let outs = Outs::new(&blind, data);
+    assert!(close(outs.straight(&held(&blind), 1), 4. / 48.));
+}
+
+#[test]
+fn wild_and_stone_outs() {
+    let mut run = RunCreator::builder().seed("AAAAAAAA".to_string()).build().create();
+    let find = |data: &RunData, suit: Suit, rank: Rank| {
+        data.cards.iter().position(|card| card.suit == suit && card.rank == rank).unwrap()
+    };
+    for rank in [Ace, King, Queen] {
+        let idx = find(&run.data, Spade, rank);
+        run.data.cards[idx].enhancement = WildCard;
+    }
+    for (suit, rank) in [(Heart, Ace), (Heart, Rank2)] {
+        let idx = find(&run.data, suit, rank);
+        run.data.cards[idx].enhancement = StoneCard;
+    }
+    let data = &run.data;
+    let held = |blind: &Blind| blind.held.iter().map(|&idx| &data.cards[idx]).collect::<Vec<_>>();
+
+    // 7 hearts, 3 wild spades and no stone
+    let blind = holding(data, &[(Heart, Rank3), (Heart, Rank7), (Heart, Jack), (Heart, King)]);
+    let outs = Outs::new(&blind, data);
+    assert!(close(outs.flush(&held(&blind), 1), 10. / 48.));
+
+    // A held wild spade counts as a heart, leaving 8 hearts and 2 wild spades
+    let blind = holding(data, &[(Heart, Rank3), (Heart, Rank7), (Heart, Jack), (Spade, Ace)]);
+    let outs = Outs::new(&blind, data);
+    assert!(close(outs.flush(&held(&blind), 1), 10. / 48.));
+
+    // The stone 2♥ is no two
+    let blind = holding(data, &[(Club, Rank2), (Diamond, Rank2)]);
+    let outs = Outs::new(&blind, data);
+    assert!(close(outs.of_a_kind(&held(&blind), 3, 1), 1. / 50.));
+}
@@ -0,0 +1,67 @@
+use balatro_logic::{
+    blind::{
+        Blind,
+        BlindType::{Big, Boss, Small},
+        BossBlindType::{TheHouse, TheManacle},
+    },
+    builders::run::RunCreator,
+    consumable::{Consumable::TarotCard, Tarot},
+    forecast::Forecaster,
+    joker::JokerType::{Bull, FacelessJoker, Misprint},
+    shop::ShopItem,
+    tags::Tag::{EconomyTag, EtherealTag, JuggleTag, SpeedTag},
+};
+
+#[test]
+fn first_antes() {
+    let run = RunCreator::builder().seed("AAAAAAAA".to_string()).build().create();
+    let rng_state = run.data.rng.pseudorandom_state.clone();
+
+    let antes = Forecaster::new(&run, 1).take(2).collect::<Vec<_>>();
+    assert_eq!(run.data.rng.pseudorandom_state, rng_state);
+
+    let [first, second] = &antes[..] else { unreachable!() };
+    assert_eq!((first.ante, first.tags, first.boss), (1, Some([SpeedTag, EconomyTag]), TheManacle));
+    assert_eq!(
+        (second.ante, second.tags, second.boss),
+        (2, Some([JuggleTag, EtherealTag]), TheHouse)
+    );
+
+    let after = first.shops.iter().map(|shop| shop.after).collect::<Vec<_>>();
+    assert_eq!(after, [Small, Big, Boss(TheManacle)]);
+
+    let joker_types = |inventory: &[ShopItem]| {
+        inventory
+            .iter()
+            .map(|item| match item {
+                ShopItem::Joker(joker) => Some(joker.joker_type),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let inventories = &first.shops[0].inventories;
+    assert_eq!(joker_types(&inventories[0]), [Some(Bull), Some(FacelessJoker)]);
+    assert_eq!(joker_types(&inventories[1])[0], Some(Misprint));
+    assert_eq!(inventories[1][1], ShopItem::Consumable(TarotCard(Tarot::TheSun)));
+
+    assert_eq!(first.shops[0].vouchers, run.data.shop.vouchers);
+    assert_eq!(first.shops[1].vouchers, first.shops[0].vouchers);
+
+    let mut next_ante = run.data.clone();
+    next_ante.ante += 1;
+    assert_eq!(first.shops[2].vouchers, [next_ante.poll_next_voucher()]);
+    assert_eq!(second.shops[0].vouchers, first.shops[2].vouchers);
+    assert_ne!(first.shops[2].vouchers, first.shops[1].vouchers);
+}
+
+#[test]
+fn mid_ante_keeps_rolled_tags() {
+    let mut run = RunCreator::builder().seed("AAAAAAAA".to_string()).build().create();
+    run.data.end_round(&Blind { blind_type: Small, ..Default::default() });
+
+    let antes = Forecaster::new(&run, 0).take(2).collect::<Vec<_>>();
+    let [first, second] = &antes[..] else { unreachable!() };
+    assert_eq!((first.ante, first.tags, first.shops.len()), (1, None, 2));
+    assert_eq!(second.tags, Some([JuggleTag, EtherealTag]));
+}
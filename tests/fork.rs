@@ -0,0 +1,43 @@
+use balatro_logic::{
+    boosters::BoosterPackType::BuffoonNormal, builders::run::RunCreator, game_state::GameState,
+};
+
+#[test]
+fn forks_replay_identically() {
+    let mut run = RunCreator::builder().seed("AAAAAAAA".to_string()).build().create();
+    run.data.money = 20.;
+    let mut fork = run.fork();
+
+    for run in [&mut run, &mut fork] {
+        run.enter_shop(true);
+        run.reroll().unwrap();
+    }
+
+    assert_eq!(run.data.shop.inventory, fork.data.shop.inventory);
+    assert_eq!(run.data.shop.packs, fork.data.shop.packs);
+    assert_eq!(run.data.money, fork.data.money);
+    assert_eq!(run.open_booster_pack(BuffoonNormal), fork.open_booster_pack(BuffoonNormal));
+}
+
+#[test]
+fn forks_are_independent() {
+    let run = RunCreator::builder().seed("AAAAAAAA".to_string()).build().create();
+    let rng_state = run.data.rng.pseudorandom_state.clone();
+
+    let mut fork = run.fork();
+    fork.enter_shop(true);
+    if let GameState::Blind(blind) = &mut fork.game_state {
+        blind.select(0).unwrap();
+    }
+
+    assert_eq!(run.data.rng.pseudorandom_state, rng_state);
+    assert!(run.data.shop.inventory.is_empty());
+
+    let (GameState::Blind(original), GameState::Blind(forked)) =
+        (&run.game_state, &fork.game_state)
+    else {
+        unreachable!()
+    };
+    assert_eq!(original.held, forked.held);
+    assert_ne!(original.selected, forked.selected);
+}
@@ -0,0 +1,46 @@
+use balatro_logic::{
+    card::{
+        Card,
+        Enhancement::{StoneCard, WildCard},
+        Rank::{self, *},
+        Suit::*,
+    },
+    hands::{Hand, HandType, ResolvedHand},
+};
+
+fn hand_type(ranks: [Rank; 5]) -> HandType {
+    let suits = [Spade, Heart, Club, Diamond, Spade];
+    let cards =
+        ranks.iter().zip(suits).map(|(&rank, suit)| Card::new(suit, rank)).collect::<Vec<_>>();
+    ResolvedHand(cards.iter().collect()).hand_type()
+}
+
+#[test]
+fn straights() {
+    assert_eq!(hand_type([Rank2, Rank3, Rank4, Rank5, Rank6]), HandType::Straight);
+    assert_eq!(hand_type([Rank6, Rank4, Rank2, Rank5, Rank3]), HandType::Straight);
+    assert_eq!(hand_type([Rank10, Jack, Queen, King, Ace]), HandType::Straight);
+    assert_eq!(hand_type([Ace, Rank2, Rank3, Rank4, Rank5]), HandType::Straight);
+}
+
+#[test]
+fn near_straights() {
+    assert_eq!(hand_type([Rank2, Rank3, Rank4, Rank5, Rank7]), HandType::HighCard);
+    assert_eq!(hand_type([Queen, King, Ace, Rank2, Rank3]), HandType::HighCard);
+    assert_eq!(hand_type([Rank2, Rank2, Rank3, Rank4, Rank5]), HandType::Pair);
+}
+
+#[test]
+fn wild_and_stone_cards() {
+    let mut cards = [Rank2, Rank5, Rank8, Jack, Ace].map(|rank| Card::new(Heart, rank)).to_vec();
+    cards[0].suit = Spade;
+    cards[0].enhancement = WildCard;
+    assert_eq!(ResolvedHand(cards.iter().collect()).hand_type(), HandType::Flush);
+
+    cards[0].enhancement = StoneCard;
+    assert_eq!(ResolvedHand(cards.iter().collect()).hand_type(), HandType::HighCard);
+
+    let hand = Hand { cards: [0, 1, 2, 3, 4], len: 5 };
+    assert_eq!(hand.scoring(&cards, false), [0, 4]);
+    assert_eq!(hand.scoring(&cards, true), [0, 1, 2, 3, 4]);
+}
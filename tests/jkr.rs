@@ -0,0 +1,396 @@
+use balatro_logic::{
+    blind::BossBlindType::TheWall,
+    boosters::BoosterPackType::{ArcanaNormal, BuffoonJumbo},
+    builders::run::RunCreator,
+    card::{Card, Edition, Enhancement, Rank, Seal, Suit},
+    consumable::{Consumable, Tarot},
+    decks::DeckType,
+    game_state::GameState,
+    hands::HandType,
+    jkr::{JkrError, compress, decompress},
+    joker::{JokerEdition, JokerInternalState, JokerType},
+    run::Run,
+    shop::ShopItem,
+    stake::Stake,
+    tags::Tag,
+    vouchers::Voucher,
+};
+use itertools::Itertools;
+use mlua::{Lua, Table};
+use strum::IntoEnumIterator;
+
+fn save(state: u32) -> String {
+    let hands = HandType::iter()
+        .map(|hand| {
+            let (level, played) = if hand == HandType::Pair { (3, 4) } else { (1, 0) };
+            format!(
+                r#"["{}"]={{level={level},chips={},mult={},played={played},played_this_round={},visible=true}}"#,
+                hand.game_name(),
+                10 * level,
+                2 * level,
+                played.min(1),
+            )
+        })
+        .join(",");
+
+    format!(
+        r#"return {{
+            STATE={state},
+            BACK={{key="b_blue"}},
+            BLIND={{chips=600}},
+            tags={{{{key="tag_negative"}}}},
+            GAME={{
+                stake=2, dollars=12, interest_cap=25, chips=150,
+                edition_rate=1, discount_percent=25,
+                joker_rate=20, tarot_rate=4, planet_rate=4, playing_card_rate=0, spectral_rate=0,
+                shop={{joker_max=2}},
+                starting_params={{ante_scaling=1}},
+                modifiers={{money_per_discard=1}},
+                used_vouchers={{v_clearance_sale=true}},
+                bosses_used={{bl_hook=1}},
+                blind_on_deck="Big",
+                round_resets={{ante=2, hands=5, discards=3, reroll_cost=5, temp_reroll_cost=nil,
+                    blind_choices={{Boss="bl_wall"}}}},
+                current_round={{reroll_cost=6, hands_left=3, discards_left=2}},
+                pseudorandom={{seed="ABCDEFGH", hashed_seed=0.5, boss=0.25, ["Joker1sho2"]=0.75}},
+                hands={{{hands}}},
+            }},
+            cardAreas={{
+                deck={{config={{card_limit=52}}, cards={{
+                    {{sort_id=3, save_fields={{card="H_T", center="m_glass"}}, ability={{perma_bonus=5}},
+                        edition={{polychrome=true}}, seal="Red"}},
+                    {{sort_id=1, save_fields={{card="S_2", center="c_base"}}, ability={{}}}},
+                }}}},
+                hand={{config={{card_limit=8}}, cards={{
+                    {{sort_id=2, save_fields={{card="D_A", center="m_stone"}}, ability={{}}, seal="Gold"}},
+                }}}},
+                jokers={{config={{card_limit=5}}, cards={{
+                    {{save_fields={{center="j_ceremonial"}}, ability={{mult=6, eternal=true}},
+                        edition={{negative=true}}, sell_cost=3}},
+                    {{save_fields={{center="j_card_sharp"}}, ability={{}}, sell_cost=3}},
+                }}}},
+                consumeables={{config={{card_limit=2}}, cards={{
+                    {{save_fields={{center="c_heirophant"}}, ability={{}}}},
+                }}}},
+                shop_jokers={{config={{card_limit=2}}, cards={{
+                    {{save_fields={{center="j_ring_master"}}, ability={{}}, sell_cost=2}},
+                    {{save_fields={{center="c_mercury"}}, ability={{}}}},
+                }}}},
+                shop_vouchers={{config={{card_limit=1}}, cards={{
+                    {{save_fields={{center="v_grabber"}}, ability={{}}}},
+                }}}},
+                shop_booster={{config={{card_limit=2}}, cards={{
+                    {{save_fields={{center="p_arcana_normal_3"}}, ability={{}}}},
+                    {{save_fields={{center="p_buffoon_jumbo_1"}}, ability={{}}}},
+                }}}},
+            }},
+        }}"#
+    )
+}
+
+#[test]
+fn load_shop_save() {
+    let run = Run::from_jkr(&compress(&save(5))).unwrap();
+    let data = &run.data;
+
+    assert!(matches!(run.game_state, GameState::Shop));
+    assert_eq!(data.stake, Stake::Red);
+    assert_eq!(data.deck_type, DeckType::Blue);
+    assert_eq!((data.ante, data.money, data.hand_size), (2, 12., 8));
+    assert_eq!((data.starting_hands, data.starting_discards), (5, 3));
+    assert_eq!(data.this_antes_boss, TheWall);
+    assert_eq!(data.times_boss_used[balatro_logic::blind::BossBlindType::TheHook as usize], 1);
+    assert_eq!(data.tags, vec![Tag::NegativeTag]);
+    assert_eq!(data.money_per_discard, 1.);
+    assert!(data.vouchers[Voucher::ClearanceSale as usize]);
+
+    assert_eq!(data.rng.seed, "ABCDEFGH");
+    assert_eq!(data.rng.pseudorandom_state.get("boss"), Some(&0.25));
+    assert_eq!(data.rng.pseudorandom_state.get("Joker1sho2"), Some(&0.75));
+    assert!(!data.rng.pseudorandom_state.contains_key("seed"));
+
+    let pair = HandType::Pair as usize;
+    assert_eq!((data.hand_levels[pair], data.base_chips[pair], data.base_mult[pair]), (3, 30, 6));
+    assert_eq!(data.times_played[pair], 4);
+
+    let mut ten = Card::new(Suit::Heart, Rank::Rank10);
+    ten.enhancement = Enhancement::GlassCard;
+    ten.edition = Edition::Polychrome;
+    ten.seal = Seal::Red;
+    ten.chips += 5;
+    let mut ace = Card::new(Suit::Diamond, Rank::Ace);
+    ace.enhancement = Enhancement::StoneCard;
+    ace.seal = Seal::Gold;
+    assert_eq!(data.cards, vec![Card::new(Suit::Spade, Rank::Rank2), ace, ten]);
+
+    assert_eq!(run.jokers.len(), 2);
+    let dagger = &run.jokers[0];
+    assert_eq!(dagger.data, JokerInternalState::CeremonialDagger { mult: 6 });
+    assert_eq!(dagger.edition, JokerEdition::Negative);
+    assert!(dagger.stickers.eternal);
+    let JokerInternalState::CardSharp { played_before } = run.jokers[1].data else {
+        panic!("Card Sharp should keep its state")
+    };
+    assert!(played_before[pair]);
+
+    assert_eq!(data.consumables, vec![Consumable::TarotCard(Tarot::TheHierophant)]);
+    assert_eq!(data.shop.price_multiplier, 0.75);
+    assert_eq!(data.shop.reroll_price, 6.);
+    assert_eq!(data.shop.vouchers, vec![Voucher::Grabber]);
+    assert_eq!(data.shop.packs, [Some(ArcanaNormal), Some(BuffoonJumbo)]);
+    assert!(matches!(
+        &data.shop.inventory[..],
+        [ShopItem::Joker(joker), ShopItem::Consumable(Consumable::PlanetCard(HandType::Pair))]
+            if joker.joker_type == JokerType::Showman
+    ));
+}
+
+#[test]
+fn load_blind_save() {
+    let run = Run::from_jkr(&compress(&save(1))).unwrap();
+
+    let GameState::Blind(blind) = &run.game_state else {
+        panic!("expected a blind, got {:?}", run.game_state)
+    };
+    assert_eq!((blind.score, blind.requirement), (150., 600.));
+    assert_eq!((blind.hands, blind.discards), (3, 2));
+    assert_eq!(blind.held, vec![1]);
+    assert_eq!(blind.cards, vec![2, 0]);
+}
+
+#[test]
+fn rejects_unknown_states() {
+    assert!(matches!(Run::from_jkr(&compress(&save(4))), Err(JkrError::UnsupportedState(4))));
+    assert!(matches!(Run::from_jkr(b"not deflate"), Err(JkrError::Io(_) | JkrError::Lua(_))));
+}
+
+#[test]
+fn export_round_trip() {
+    let mut run = RunCreator::builder().seed("ROUNDTRP".to_string()).build().create();
+    let GameState::Blind(blind) = &mut run.game_state else { unreachable!() };
+    blind.select(0).unwrap();
+    blind.select(1).unwrap();
+    blind.discard().unwrap();
+    blind.draw(&run.jokers, &mut run.data);
+    blind.face_down = vec![blind.held[2], blind.held[5]];
+
+    let loaded = Run::from_jkr(&run.to_jkr()).unwrap();
+    assert_eq!(loaded.data.rng.pseudorandom_state, run.data.rng.pseudorandom_state);
+    assert_eq!(loaded.data.rng.hashed_seed, run.data.rng.hashed_seed);
+    assert_eq!(loaded.data.cards, run.data.cards);
+    assert_eq!(loaded.data.times_played, run.data.times_played);
+    let (GameState::Blind(original), GameState::Blind(blind)) =
+        (&run.game_state, &loaded.game_state)
+    else {
+        unreachable!()
+    };
+    assert_eq!((blind.cards.len(), &blind.held), (original.cards.len(), &original.held));
+    assert_eq!((blind.hands, blind.discards), (original.hands, original.discards));
+    assert_eq!(blind.face_down, original.face_down);
+
+    let mut run = loaded;
+    run.data.money = 50.;
+    run.data.vouchers[Voucher::Overstock as usize] = true;
+    run.data.consumables.push(Consumable::TarotCard(Tarot::TheFool));
+    run.data.consumable_extra_value = vec![3];
+    run.enter_shop(true);
+    run.game_state = GameState::Shop;
+    let loaded = Run::from_jkr(&run.to_jkr()).unwrap();
+    assert!(matches!(loaded.game_state, GameState::Shop));
+    assert_eq!(loaded.data.shop.inventory, run.data.shop.inventory);
+    assert_eq!(loaded.data.shop.packs, run.data.shop.packs);
+    assert_eq!(loaded.data.shop.vouchers, run.data.shop.vouchers);
+    assert_eq!(loaded.data.vouchers, run.data.vouchers);
+    assert_eq!(loaded.data.consumable_extra_value, [3]);
+    assert_eq!(loaded.data.money, run.data.money);
+    assert_eq!(loaded.data.rng.pseudorandom_state, run.data.rng.pseudorandom_state);
+
+    run.game_state = GameState::CashOut { earnings: 7. };
+    let loaded = Run::from_jkr(&run.to_jkr()).unwrap();
+    assert!(matches!(loaded.game_state, GameState::CashOut { earnings: 7. }));
+    assert_eq!(loaded.data.blind_on_deck, run.data.blind_on_deck);
+}
+
+/// Keys `Card:save()`, `Card:set_ability`, `Card:set_base`, `CardArea:save()`, `Blind:save()`
+/// and `Game:init_game_object` put in a real save.
+const CARD_KEYS: [&str; 15] = [
+    "sort_id",
+    "save_fields",
+    "params",
+    "base_cost",
+    "extra_cost",
+    "cost",
+    "sell_cost",
+    "facing",
+    "sprite_facing",
+    "highlighted",
+    "debuff",
+    "added_to_deck",
+    "label",
+    "base",
+    "ability",
+];
+const ABILITY_KEYS: [&str; 17] = [
+    "name",
+    "effect",
+    "set",
+    "mult",
+    "h_mult",
+    "h_x_mult",
+    "h_dollars",
+    "p_dollars",
+    "t_mult",
+    "t_chips",
+    "x_mult",
+    "h_size",
+    "d_size",
+    "extra_value",
+    "type",
+    "perma_bonus",
+    "bonus",
+];
+const BASE_KEYS: [&str; 9] = [
+    "name",
+    "suit",
+    "value",
+    "original_value",
+    "id",
+    "nominal",
+    "suit_nominal",
+    "face_nominal",
+    "times_played",
+];
+const BLIND_KEYS: [&str; 8] =
+    ["name", "config_blind", "chips", "chip_text", "mult", "dollars", "boss", "disabled"];
+const GAME_KEYS: [&str; 24] = [
+    "won",
+    "round_scores",
+    "win_ante",
+    "stake",
+    "modifiers",
+    "starting_params",
+    "round",
+    "probabilities",
+    "bosses_used",
+    "pseudorandom",
+    "edition_rate",
+    "joker_rate",
+    "dollars",
+    "interest_cap",
+    "blind",
+    "selected_back",
+    "blind_on_deck",
+    "tags",
+    "used_vouchers",
+    "current_round",
+    "round_resets",
+    "shop",
+    "cards_played",
+    "hands",
+];
+const HAND_KEYS: [&str; 10] = [
+    "visible", "order", "mult", "chips", "s_mult", "s_chips", "level", "l_mult", "l_chips",
+    "example",
+];
+const AREAS: [&str; 9] = [
+    "deck",
+    "hand",
+    "discard",
+    "play",
+    "jokers",
+    "consumeables",
+    "shop_jokers",
+    "shop_vouchers",
+    "shop_booster",
+];
+
+fn eval(lua: &Lua, text: &str) -> Table {
+    lua.load(text).set_environment(lua.create_table().unwrap()).eval().unwrap()
+}
+
+fn assert_keys(table: &Table, keys: &[&str], what: &str) {
+    for key in keys {
+        assert!(table.contains_key(*key).unwrap(), "{what} is missing `{key}`");
+    }
+}
+
+#[test]
+fn export_matches_the_save_layout() {
+    let run = Run::from_jkr(&compress(&save(1))).unwrap();
+    let lua = Lua::new();
+    let save = eval(&lua, &run.to_jkr_table());
+
+    for key in ["VERSION", "STATE", "BACK", "BLIND", "tags", "GAME", "cardAreas"] {
+        assert!(save.contains_key(key).unwrap(), "save is missing `{key}`");
+    }
+    assert_keys(&save.get("BLIND").unwrap(), &BLIND_KEYS, "BLIND");
+
+    let game: Table = save.get("GAME").unwrap();
+    assert_keys(&game, &GAME_KEYS, "GAME");
+    let hands: Table = game.get("hands").unwrap();
+    for hand_type in HandType::iter() {
+        assert_keys(&hands.get(hand_type.game_name()).unwrap(), &HAND_KEYS, hand_type.game_name());
+    }
+    let resets: Table = game.get("round_resets").unwrap();
+    assert_keys(&resets, &["ante", "blind_ante", "blind_states", "blind_choices"], "round_resets");
+
+    let areas: Table = save.get("cardAreas").unwrap();
+    for name in AREAS {
+        let area: Table = areas.get(name).unwrap();
+        assert_keys(&area.get("config").unwrap(), &["card_limit", "type"], name);
+
+        for card in area.get::<Table>("cards").unwrap().sequence_values::<Table>() {
+            let card = card.unwrap();
+            assert_keys(&card, &CARD_KEYS, name);
+            assert_keys(&card.get("ability").unwrap(), &ABILITY_KEYS, name);
+            if card.get::<Table>("save_fields").unwrap().contains_key("card").unwrap() {
+                assert_keys(&card.get("base").unwrap(), &BASE_KEYS, name);
+            }
+        }
+    }
+
+    let card = |area: &str| -> Table {
+        areas.get::<Table>(area).unwrap().get::<Table>("cards").unwrap().get(1).unwrap()
+    };
+    let dagger = card("jokers");
+    let ability: Table = dagger.get("ability").unwrap();
+    assert_eq!(ability.get::<String>("name").unwrap(), "Ceremonial Dagger");
+    assert_eq!(ability.get::<f64>("mult").unwrap(), 6.);
+    assert!(ability.get::<bool>("eternal").unwrap());
+    let hierophant: Table = card("consumeables").get("ability").unwrap();
+    assert_eq!(
+        hierophant.get::<Table>("consumeable").unwrap().get::<u32>("max_highlighted").unwrap(),
+        2
+    );
+    let ace: Table = card("hand").get("base").unwrap();
+    assert_eq!(
+        (ace.get::<String>("value").unwrap(), ace.get::<u32>("id").unwrap()),
+        ("Ace".into(), 14)
+    );
+
+    let reloaded = Run::from_jkr_table(&run.to_jkr_table()).unwrap();
+    assert_eq!(reloaded.data.cards, run.data.cards);
+    assert_eq!(reloaded.jokers, run.jokers);
+}
+
+#[test]
+fn patching_keeps_unknown_fields() {
+    let original = save(5).replace("stake=2,", "stake=2, round_scores={hand={amt=1234}},");
+    let mut run = Run::from_jkr(&compress(&original)).unwrap();
+    run.data.money = 40.;
+
+    let patched = decompress(&run.patch_jkr(&compress(&original)).unwrap()).unwrap();
+    let lua = Lua::new();
+    let game: Table = eval(&lua, &patched).get("GAME").unwrap();
+    assert_eq!(
+        game.get::<Table>("round_scores")
+            .unwrap()
+            .get::<Table>("hand")
+            .unwrap()
+            .get::<u32>("amt")
+            .unwrap(),
+        1234
+    );
+    assert_eq!(game.get::<f64>("dollars").unwrap(), 40.);
+    assert_eq!(Run::from_jkr_table(&patched).unwrap().data.money, 40.);
+}
@@ -0,0 +1,90 @@
+use balatro_logic::{
+    blind::{
+        Blind,
+        BlindType::Boss,
+        BossBlindType::{TheHouse, TheMark},
+    },
+    builders::run::RunCreator,
+    card::Rank::{Jack, King, Queen},
+    controller::{BlindAction, Controller, SimulationResult},
+    draw::Outs,
+    game_state::GameState,
+    observation::{BlindView, Observation},
+    vouchers::Voucher::Overstock,
+};
+
+#[test]
+fn blind_view() {
+    let mut run = RunCreator::builder().seed("AAAAAAAA".to_string()).build().create();
+    run.data.vouchers[Overstock as usize] = true;
+    let GameState::Blind(blind) = &mut run.game_state else { unreachable!() };
+    blind.select(5).unwrap();
+    blind.select(2).unwrap();
+
+    let view = BlindView::new(blind, &run.data);
+    let held = view.held.iter().map(Option::as_ref).collect::<Vec<_>>();
+    assert_eq!(held, run.data.resolve(&blind.held).into_iter().map(Some).collect::<Vec<_>>());
+    assert_eq!(view.selected, [2, 5]);
+    assert_eq!(view.deck, Outs::new(blind, &run.data));
+    assert_eq!(view.deck.remaining.len(), blind.cards.len());
+
+    let observation = Observation::new(&run);
+    assert_eq!(observation.money, run.data.money);
+    assert_eq!(observation.vouchers, [Overstock]);
+}
+
+#[test]
+fn controllers_only_observe() {
+    #[derive(Default)]
+    struct PlayOnce(Vec<BlindView>);
+
+    impl Controller for PlayOnce {
+        fn blind(&mut self, _: &Observation, blind: &BlindView) -> Vec<BlindAction> {
+            self.0.push(blind.clone());
+
+            match self.0.len() {
+                1 => vec![BlindAction::SelectCard(0), BlindAction::Play],
+                _ => vec![BlindAction::Abort],
+            }
+        }
+    }
+
+    let run = RunCreator::builder().seed("AAAAAAAA".to_string()).build().create();
+    let mut controller = PlayOnce::default();
+    assert_eq!(run.simulate(&mut controller), SimulationResult::Aborted);
+
+    let [first, second] = &controller.0[..] else { unreachable!() };
+    assert_eq!(second.hands, first.hands - 1);
+    assert!(second.score > 0.);
+    assert_eq!(second.held.len(), 8);
+    assert_eq!(second.held[..7], first.held[1..]);
+    assert_eq!(second.deck.remaining.len(), first.deck.remaining.len() - 1);
+}
+
+#[test]
+fn face_down_cards_stay_hidden() {
+    let mut run = RunCreator::builder().seed("AAAAAAAA".to_string()).build().create();
+    let deck = (0..run.data.cards.len()).collect::<Vec<_>>();
+
+    let mut house =
+        Blind { blind_type: Boss(TheHouse), cards: deck.clone(), hands: 2, ..Default::default() };
+    house.draw(&run.jokers, &mut run.data);
+    assert_eq!(house.face_down, house.held);
+    assert!(BlindView::new(&house, &run.data).held.iter().all(Option::is_none));
+
+    house.select(0).unwrap();
+    house.prepare_play(&run.data).unwrap();
+    house.draw(&run.jokers, &mut run.data);
+    let view = BlindView::new(&house, &run.data);
+    assert_eq!(house.face_down.len(), 7);
+    assert!(view.held[..7].iter().all(Option::is_none));
+    assert!(view.held[7].is_some());
+
+    let mut mark = Blind { blind_type: Boss(TheMark), cards: deck, hands: 1, ..Default::default() };
+    mark.draw(&run.jokers, &mut run.data);
+    let view = BlindView::new(&mark, &run.data);
+    for (card, &idx) in view.held.iter().zip(&mark.held) {
+        let face = matches!(run.data.cards[idx].rank, Jack | Queen | King);
+        assert_eq!(card.is_none(), face);
+    }
+}
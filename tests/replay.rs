@@ -0,0 +1,126 @@
+use balatro_logic::{
+    boosters::BoosterPack,
+    builders::run::RunCreator,
+    controller::{BlindAction, Controller, PackAction, ShopAction, SimulationResult},
+    decks::DeckType,
+    observation::{BlindView, Observation, ShopView},
+    replay::{Decision, Replay, ReplayError},
+    stake::Stake,
+};
+
+/// Plays the first five held cards every hand.
+struct FirstFive;
+
+impl Controller for FirstFive {
+    fn blind(&mut self, _: &Observation, blind: &BlindView) -> Vec<BlindAction> {
+        (0..blind.held.len().min(5))
+            .map(BlindAction::SelectCard)
+            .chain([BlindAction::Play])
+            .collect()
+    }
+}
+
+/// [`FirstFive`], opening the first pack of every shop and taking its first card.
+struct PackOpener;
+
+impl Controller for PackOpener {
+    fn shop(&mut self, _: &Observation, _: &ShopView) -> Vec<ShopAction> {
+        vec![ShopAction::BuyPack(0), ShopAction::ExitShop]
+    }
+
+    fn pack(&mut self, _: &Observation, _: &BoosterPack) -> Vec<PackAction> {
+        vec![PackAction::Pick(0)]
+    }
+
+    fn blind(&mut self, observation: &Observation, blind: &BlindView) -> Vec<BlindAction> {
+        FirstFive.blind(observation, blind)
+    }
+}
+
+fn record() -> Replay {
+    let creator = RunCreator::builder()
+        .deck(DeckType::Blue)
+        .stake(Stake::Red)
+        .seed("REPLAY12".to_string())
+        .build();
+
+    Replay::record(creator, FirstFive)
+}
+
+#[test]
+fn replays_verify() {
+    let replay = record();
+
+    assert_eq!(
+        (replay.seed.as_str(), replay.deck, replay.stake),
+        ("REPLAY12", DeckType::Blue, Stake::Red)
+    );
+    assert!(matches!(replay.result, SimulationResult::Lost { .. }));
+    assert!(replay.decisions.iter().any(|decision| matches!(decision, Decision::Cashout(_))));
+    assert_eq!(replay.checkpoints.len(), replay.decisions.len());
+    assert_eq!(replay, record());
+    assert_eq!(replay.verify(), Ok(()));
+}
+
+#[test]
+fn pack_picks_are_recorded() {
+    let creator = RunCreator::builder().seed("REPLAY12".to_string()).build();
+    let replay = Replay::record(creator, PackOpener);
+
+    let pack = replay.decisions.iter().position(|d| matches!(d, Decision::Pack(_))).unwrap();
+    assert_eq!(
+        replay.decisions[pack - 1],
+        Decision::Shop(vec![ShopAction::BuyPack(0), ShopAction::ExitShop])
+    );
+    assert_eq!(replay.decisions[pack], Decision::Pack(vec![PackAction::Pick(0)]));
+    assert_eq!(replay.verify(), Ok(()));
+
+    let mut skipped = replay.clone();
+    skipped.decisions.remove(pack);
+    skipped.checkpoints.remove(pack);
+    assert_eq!(skipped.verify(), Err(ReplayError::Mismatch { index: pack }));
+}
+
+#[test]
+fn tampered_replays_fail() {
+    let mut truncated = record();
+    truncated.decisions.pop();
+    assert_eq!(truncated.verify(), Err(ReplayError::Exhausted));
+
+    let mut swapped = record();
+    let cashout = swapped.decisions.iter().position(|d| matches!(d, Decision::Cashout(_))).unwrap();
+    swapped.decisions.swap(cashout - 1, cashout);
+    assert_eq!(swapped.verify(), Err(ReplayError::Mismatch { index: cashout - 1 }));
+
+    let mut changed = record();
+    let Some(Decision::Blind(actions)) =
+        changed.decisions.iter_mut().rfind(|d| matches!(d, Decision::Blind(_)))
+    else {
+        unreachable!()
+    };
+    actions.retain(|action| matches!(action, BlindAction::SelectCard(0) | BlindAction::Play));
+    assert!(matches!(changed.verify(), Err(ReplayError::Diverged { .. })));
+}
+
+#[test]
+fn drifting_replays_fail_at_the_next_decision() {
+    let mut changed = record();
+    let first = changed.decisions.iter().position(|d| matches!(d, Decision::Blind(_))).unwrap();
+    changed.decisions[first] = Decision::Blind(vec![BlindAction::SelectCard(1), BlindAction::Play]);
+    assert_eq!(changed.verify(), Err(ReplayError::StateMismatch { index: first + 1 }));
+
+    let mut rich = record();
+    let last = rich.checkpoints.len() - 1;
+    rich.checkpoints[last].money += 1.;
+    assert_eq!(rich.verify(), Err(ReplayError::StateMismatch { index: last }));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn replay_files() {
+    let replay = record();
+    let loaded = Replay::from_json(&replay.to_json().unwrap()).unwrap();
+
+    assert_eq!(loaded, replay);
+    assert_eq!(loaded.verify(), Ok(()));
+}
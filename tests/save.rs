@@ -0,0 +1,59 @@
+#![cfg(feature = "serde")]
+
+use balatro_logic::{
+    builders::run::RunCreator,
+    run::Run,
+    save::{SAVE_VERSION, SaveError},
+    shop::ShopItem,
+};
+use std::collections::HashMap;
+
+fn mid_game_run() -> Run {
+    let mut run = RunCreator::builder().seed("AAAAAAAA".to_string()).build().create();
+    run.data.money = 20.;
+    run.enter_shop(true);
+    run.reroll().unwrap();
+    run
+}
+
+fn continue_run(mut run: Run) -> (HashMap<String, f64>, Vec<ShopItem>, f64) {
+    run.reroll().unwrap();
+    run.enter_shop(false);
+    (run.data.rng.pseudorandom_state, run.data.shop.inventory, run.data.money)
+}
+
+#[test]
+fn json_round_trip() {
+    let run = mid_game_run();
+    let json = run.to_json().unwrap();
+    let loaded = Run::from_json(&json).unwrap();
+
+    assert_eq!(loaded.data.cards, run.data.cards);
+    assert_eq!(continue_run(loaded), continue_run(run));
+}
+
+#[test]
+fn binary_round_trip() {
+    let run = mid_game_run();
+    let bytes = run.to_bytes().unwrap();
+    let loaded = Run::from_bytes(&bytes).unwrap();
+
+    assert!(bytes.len() < run.to_json().unwrap().len());
+    assert_eq!(continue_run(loaded), continue_run(run));
+}
+
+#[test]
+fn version_mismatch() {
+    let run = mid_game_run();
+
+    let json = run.to_json().unwrap().replacen(
+        &format!("\"version\":{SAVE_VERSION}"),
+        &format!("\"version\":{}", SAVE_VERSION + 1),
+        1,
+    );
+    assert!(matches!(Run::from_json(&json), Err(SaveError::Version(v)) if v == SAVE_VERSION + 1));
+
+    let mut bytes = run.to_bytes().unwrap();
+    bytes[0] += 1;
+    assert!(matches!(Run::from_bytes(&bytes), Err(SaveError::Version(_))));
+}
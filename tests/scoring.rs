@@ -0,0 +1,214 @@
+use balatro_logic::{
+    blind::{Blind, BlindType::Boss, BossBlindType::TheEye},
+    builders::run::RunCreator,
+    card::{
+        Edition::{Foil, Polychrome},
+        Enhancement::{BonusCard, GlassCard, MultCard, SteelCard, StoneCard},
+        Seal,
+    },
+    chance::Seeded,
+    consumable::Consumable::PlanetCard,
+    decks::DeckType,
+    event::DispatcherOrder,
+    event_list::HandPlayedEventData,
+    hands::{Hand, HandType::Pair},
+    joker::{Joker, JokerEdition, JokerInternalState, JokerType, Stickers},
+    run::Run,
+    scoring::{Contribution, score_breakdown, score_hand, score_hand_observed},
+    trace::{Source, TraceEvent},
+    vouchers::Voucher::Observatory,
+};
+
+fn joker(joker_type: JokerType) -> Joker {
+    Joker {
+        data: JokerInternalState::None,
+        joker_type,
+        edition: JokerEdition::Base,
+        stickers: Stickers::default(),
+        sell_value: 0,
+        debuffed: false,
+        dispatcher_order: DispatcherOrder::default(),
+    }
+}
+
+/// Plays the pair of 2♣ and 2♦ and returns the resulting score.
+fn play_pair_of_twos(run: &mut Run) -> f64 {
+    let mut blind = Blind { hands: 1, held: vec![0, 13], ..Default::default() };
+    blind.select(0).unwrap();
+    blind.select(1).unwrap();
+
+    let hand = blind.prepare_play(&run.data).unwrap();
+    score_hand(
+        &mut run.jokers,
+        &mut run.data,
+        &mut blind,
+        &mut HandPlayedEventData { hand, allowed: true },
+    );
+
+    blind.score
+}
+
+#[test]
+fn jokers_and_observatory() {
+    let mut run = RunCreator::builder().seed("AAAAAAAA".to_string()).build().create();
+    run.jokers.push(joker(JokerType::JollyJoker));
+    run.jokers.push(joker(JokerType::TheDuo));
+    run.data.apply_voucher_effects(Observatory);
+    run.data.consumables.push(PlanetCard(Pair));
+
+    // (10 + 2 + 2) chips * ((2 + 8) * 2 * 1.5) mult
+    assert_eq!(play_pair_of_twos(&mut run), 14. * 30.);
+}
+
+#[test]
+fn plasma_balances_last() {
+    let mut run =
+        RunCreator::builder().deck(DeckType::Plasma).seed("AAAAAAAA".to_string()).build().create();
+    run.jokers.push(joker(JokerType::TheDuo));
+    run.data.apply_voucher_effects(Observatory);
+    run.data.consumables.push(PlanetCard(Pair));
+
+    // 14 chips and 2 * 2 * 1.5 = 6 mult are balanced to 10 and 10
+    assert_eq!(play_pair_of_twos(&mut run), 100.);
+}
+
+#[test]
+fn breakdown_leaves_the_run_alone() {
+    let mut run = RunCreator::builder().seed("AAAAAAAA".to_string()).build().create();
+    run.jokers.push(joker(JokerType::JollyJoker));
+    run.jokers.push(joker(JokerType::Misprint));
+    let blind = Blind { hands: 1, held: vec![0, 13], ..Default::default() };
+    let hand = Hand { cards: [0, 13, 0, 0, 0], len: 2 };
+    let rng = run.data.rng.clone();
+
+    let breakdown = score_breakdown(&run.jokers, &run.data, &blind, &hand).unwrap();
+    assert_eq!(breakdown.hand_type, Pair);
+    assert_eq!(breakdown.scoring, [0, 13]);
+    // Misprint rolls +18 mult with this seed
+    assert_eq!((breakdown.chips, breakdown.mult, breakdown.score), (14., 28., 392.));
+    assert_eq!(
+        breakdown.contributions[breakdown.contributions.len() - 2..],
+        [
+            Contribution { source: Source::Joker(0), chips: 0., mult: 8. },
+            Contribution { source: Source::Joker(1), chips: 0., mult: 18. },
+        ]
+    );
+    assert_eq!(breakdown.chance, [Source::Joker(1)]);
+
+    assert_eq!(blind, Blind { hands: 1, held: vec![0, 13], ..Default::default() });
+    assert_eq!(run.data.rng.pseudorandom_state, rng.pseudorandom_state);
+    assert_eq!(score_breakdown(&run.jokers, &run.data, &Blind::default(), &hand), None);
+
+    let mut eye = Blind {
+        hands: 2,
+        blind_type: Boss(TheEye),
+        blind_data: Boss(TheEye).default_data(),
+        ..Default::default()
+    };
+    let mut event = HandPlayedEventData { hand: hand.clone(), allowed: true };
+    eye.hand_played(&mut run.data, &mut event);
+    let breakdown = score_breakdown(&run.jokers, &run.data, &eye, &hand).unwrap();
+    assert!(!breakdown.allowed);
+    assert_eq!(breakdown.score, 0.);
+}
+
+#[test]
+fn only_scoring_cards_score() {
+    let mut run = RunCreator::builder().seed("AAAAAAAA".to_string()).build().create();
+    let blind = Blind { hands: 1, held: vec![0, 13, 3, 7, 10], ..Default::default() };
+    // 2♣ 2♦ with 5♣, 9♣ and K♣ as kickers
+    let hand = Hand { cards: [0, 13, 3, 7, 10], len: 5 };
+
+    let breakdown = score_breakdown(&run.jokers, &run.data, &blind, &hand).unwrap();
+    assert_eq!(breakdown.scoring, [0, 13]);
+    assert_eq!((breakdown.chips, breakdown.mult), (14., 2.));
+
+    run.data.cards[3].enhancement = StoneCard;
+    let breakdown = score_breakdown(&run.jokers, &run.data, &blind, &hand).unwrap();
+    assert_eq!(breakdown.hand_type, Pair);
+    assert_eq!(breakdown.scoring, [0, 13, 3]);
+    assert_eq!(breakdown.chips, 64.);
+
+    run.jokers.push(joker(JokerType::Splash));
+    let breakdown = score_breakdown(&run.jokers, &run.data, &blind, &hand).unwrap();
+    assert_eq!(breakdown.scoring, [0, 13, 3, 7, 10]);
+    assert_eq!(breakdown.chips, 64. + 9. + 10.);
+}
+
+#[test]
+fn card_effects() {
+    let mut run = RunCreator::builder().seed("AAAAAAAA".to_string()).build().create();
+    let cards = &mut run.data.cards;
+    (cards[0].enhancement, cards[0].edition, cards[0].seal) = (BonusCard, Foil, Seal::Red);
+    (cards[13].enhancement, cards[13].edition) = (MultCard, Polychrome);
+    cards[20].enhancement = SteelCard;
+    let blind = Blind { hands: 1, held: vec![0, 13, 20], ..Default::default() };
+    let hand = Hand { cards: [0, 13, 0, 0, 0], len: 2 };
+
+    let breakdown = score_breakdown(&run.jokers, &run.data, &blind, &hand).unwrap();
+    // 10 + (2 + 30 + 50) * 2 + 2 chips, (2 + 4) * 1.5 * 1.5 mult
+    assert_eq!((breakdown.chips, breakdown.mult), (176., 13.5));
+    assert_eq!(
+        breakdown.contributions[..4],
+        [
+            Contribution { source: Source::Card(0), chips: 82., mult: 0. },
+            Contribution { source: Source::Card(0), chips: 82., mult: 0. },
+            Contribution { source: Source::Card(13), chips: 2., mult: 7. },
+            Contribution { source: Source::Card(20), chips: 0., mult: 4.5 },
+        ]
+    );
+}
+
+#[test]
+fn space_joker_levels_up_before_scoring() {
+    let mut run = RunCreator::builder().seed("AAAAAAAA".to_string()).build().create();
+    run.jokers.push(joker(JokerType::SpaceJoker));
+    // Two Oops! All 6s make the 1 in 4 certain
+    run.jokers.push(joker(JokerType::OopsAll6s));
+    run.jokers.push(joker(JokerType::OopsAll6s));
+
+    // (10 + 15 + 2 + 2) chips * (2 + 1) mult
+    assert_eq!(play_pair_of_twos(&mut run), 29. * 3.);
+    let pair = Pair as usize;
+    assert_eq!(
+        (run.data.hand_levels[pair], run.data.base_chips[pair], run.data.base_mult[pair]),
+        (2, 25, 3)
+    );
+}
+
+#[test]
+fn glass_cards_shatter() {
+    let mut run = RunCreator::builder().seed("BBBBBBBB".to_string()).build().create();
+    for idx in [0, 13, 26, 39] {
+        run.data.cards[idx].enhancement = GlassCard;
+    }
+
+    let held = vec![0, 13, 26, 39, 50];
+    let mut blind = Blind { hands: 1, cards: vec![1, 40, 51], held, ..Default::default() };
+    blind.face_down = vec![50];
+    let hand = Hand { cards: [0, 13, 26, 39, 0], len: 4 };
+
+    let breakdown = score_breakdown(&run.jokers, &run.data, &blind, &hand).unwrap();
+    assert_eq!(breakdown.mult, 7. * 16.);
+    assert_eq!(breakdown.chance, [0, 13, 26, 39].map(Source::Card));
+
+    for idx in 0..4 {
+        blind.select(idx).unwrap();
+    }
+    let hand = blind.prepare_play(&run.data).unwrap();
+    let mut events = Vec::new();
+    let mut event = HandPlayedEventData { hand, allowed: true };
+    score_hand_observed(
+        &mut run.jokers,
+        &mut run.data,
+        &mut blind,
+        &mut event,
+        &mut Seeded,
+        &mut events,
+    );
+
+    // The 2♦ shatters, so every card after it moves down by one
+    assert_eq!(events.last(), Some(&TraceEvent::CardsDestroyed(vec![13])));
+    assert_eq!(run.data.cards.len(), 51);
+    assert_eq!((blind.cards, blind.held, blind.face_down), (vec![1, 39, 50], vec![49], vec![49]));
+}
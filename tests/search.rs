@@ -0,0 +1,115 @@
+use balatro_logic::{
+    blind::BossBlindType::{TheHouse, TheManacle},
+    builders::run::RunCreator,
+    joker::JokerType::Bull,
+    search::{
+        Candidate, Filter, SEED_SPACE, SearchMode, SeedSearch, query::QueryError, seed_from_index,
+    },
+    seeding::SEED_ALPHABET,
+    tags::Tag::SpeedTag,
+};
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicU64, Ordering},
+    },
+    thread,
+    time::Duration,
+};
+
+fn index_of(seed: &str) -> u64 {
+    seed.chars().fold(0, |idx, char| {
+        idx * SEED_ALPHABET.len() as u64
+            + SEED_ALPHABET.iter().position(|other| *other == char).unwrap() as u64
+    })
+}
+
+#[test]
+fn seed_space() {
+    assert_eq!(seed_from_index(0), "11111111");
+    assert_eq!(seed_from_index(SEED_SPACE - 1), "ZZZZZZZZ");
+    assert_eq!(seed_from_index(index_of("AAAAAAAA")), "AAAAAAAA");
+}
+
+#[test]
+fn filters() {
+    let run = RunCreator::builder().seed("AAAAAAAA".to_string()).build().create();
+    let mut candidate = Candidate::new(run, 0);
+
+    assert!(Filter::shop_joker(..=1, Bull).matches(&mut candidate));
+    assert!(Filter::boss(1, TheManacle).and(Filter::tag(1, SpeedTag)).matches(&mut candidate));
+    assert!(Filter::boss(1, TheHouse).or(Filter::boss(2, TheHouse)).matches(&mut candidate));
+    assert!(!(!Filter::boss(2, TheHouse)).matches(&mut candidate));
+    assert!(!Filter::cards(|cards| cards.len() != 52).matches(&mut candidate));
+}
+
+#[test]
+fn exhaustive_search() {
+    let start = index_of("AAAAAAAA") - 20;
+    let filter = Filter::shop_joker(..=1, Bull).and(Filter::boss(1, TheManacle));
+
+    let matches = SeedSearch::builder()
+        .filter(filter)
+        .mode(SearchMode::Exhaustive { start, count: 40 })
+        .threads(4)
+        .build()
+        .start()
+        .iter()
+        .collect::<Vec<_>>();
+
+    assert!(matches.contains(&"AAAAAAAA".to_string()));
+    assert!(matches.iter().all(|seed| (start..start + 40).contains(&index_of(seed))));
+}
+
+#[test]
+fn dropping_the_handle_stops_the_search() {
+    let checked = Arc::new(AtomicU64::new(0));
+    let counter = checked.clone();
+    let filter = Filter::new(move |_| {
+        counter.fetch_add(1, Ordering::Relaxed);
+        false
+    });
+
+    let handle = SeedSearch::builder()
+        .filter(filter)
+        .mode(SearchMode::Random { count: None })
+        .threads(2)
+        .build()
+        .start();
+    thread::sleep(Duration::from_millis(50));
+    drop(handle);
+
+    thread::sleep(Duration::from_millis(200));
+    let after_drop = checked.load(Ordering::Relaxed);
+    thread::sleep(Duration::from_millis(200));
+    assert_eq!(checked.load(Ordering::Relaxed), after_drop);
+
+    let past_the_end = SeedSearch::builder()
+        .filter(Filter::new(|_| true))
+        .mode(SearchMode::Exhaustive { start: SEED_SPACE + 5, count: 10 })
+        .build()
+        .start();
+    assert_eq!(past_the_end.iter().count(), 0);
+}
+
+#[test]
+fn queries() {
+    let matches = |query: &str| {
+        let run = RunCreator::builder().seed("AAAAAAAA".to_string()).build().create();
+        query.parse::<Filter>().unwrap().matches(&mut Candidate::new(run, 0))
+    };
+
+    assert!(matches("ante<=1 shop contains Bull; boss ante1 = TheManacle"));
+    assert!(matches("# comment\nante=2 shop !contains Bull\ntag ante1 = speedtag"));
+    assert!(matches("boss ante2 != TheManacle; ante<2 pack contains BuffoonNormal"));
+    assert!(!matches("boss ante1 != TheManacle"));
+    assert!(matches(""));
+
+    let error = |query: &str| query.parse::<Filter>().err().unwrap();
+    assert_eq!(
+        error("ante1 shop has Bull"),
+        QueryError::UnknownClause("ante1 shop has Bull".into())
+    );
+    assert_eq!(error("voucher ante<=2 = Telescope"), QueryError::InvalidAnte("ante<=2".into()));
+    assert_eq!(error("tag ante1 = Blueprint"), QueryError::UnknownName("Blueprint".into()));
+}
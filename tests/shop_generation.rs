@@ -1,30 +1,40 @@
 ﻿use balatro_logic::{
+    blind::{BlindType::Boss, BossBlindType::TheClub},
     boosters::{
-        BoosterPackData,
-        BoosterPackType::{BuffoonNormal, CelestialJumbo},
+        BoosterPackData, BoosterPackItem, BoosterPackKind, BoosterPackType,
+        BoosterPackType::{ArcanaMega, BuffoonJumbo, BuffoonNormal, CelestialJumbo, StandardMega},
+        CelestialCard, PickError,
     },
     builders::run::RunCreator,
+    card::{Edition, Enhancement, Seal},
     consumable::{Consumable::TarotCard, Tarot},
-    hands::HandType::{FourOfAKind, HighCard, Pair, ThreeOfAKind, TwoPair},
-    joker::{Joker, JokerType},
+    controller::{ActionError, BlindAction, Controller, ShopAction, SimulationResult},
+    game_state::GameState,
+    hands::{
+        HandType,
+        HandType::{FourOfAKind, HighCard, Pair, ThreeOfAKind, TwoPair},
+    },
+    joker::{Joker, JokerEdition, JokerType},
+    observation::{BlindView, Observation, ShopView},
+    run::Run,
     shop::ShopItem,
+    tags::Tag::VoucherTag,
     vouchers::Voucher,
 };
+use strum::{EnumCount, IntoEnumIterator};
 
 #[test]
 fn vouchers() {
     let mut run = RunCreator::builder().seed("AAAAAAAA".to_string()).build().create();
-    run.data.ante = 0;
+    assert_eq!(run.data.shop.vouchers, [Voucher::PlanetMerchant]);
 
     let mut next_voucher = |voucher| {
-        run.enter_shop(true);
         run.data.ante += 1;
+        run.enter_shop(true);
 
         assert_eq!(run.data.shop.vouchers[0], voucher);
     };
 
-    next_voucher(Voucher::TarotMerchant);
-    next_voucher(Voucher::PlanetMerchant);
     next_voucher(Voucher::MagicTrick);
     next_voucher(Voucher::SeedMoney);
     next_voucher(Voucher::Telescope);
@@ -34,6 +44,40 @@ fn vouchers() {
     next_voucher(Voucher::Blank);
 }
 
+#[test]
+fn voucher_restocks_after_boss() {
+    #[derive(Default)]
+    struct BeatBoss(Vec<Voucher>);
+
+    impl Controller for BeatBoss {
+        fn shop(&mut self, _: &Observation, shop: &ShopView) -> Vec<ShopAction> {
+            self.0 = shop.vouchers.clone();
+            vec![ShopAction::ExitShop]
+        }
+
+        fn blind(&mut self, _: &Observation, _: &BlindView) -> Vec<BlindAction> {
+            match self.0.is_empty() {
+                true => (0..5).map(BlindAction::SelectCard).chain([BlindAction::Play]).collect(),
+                false => vec![BlindAction::Abort],
+            }
+        }
+    }
+
+    let mut run = RunCreator::builder().seed("AAAAAAAA".to_string()).build().create();
+    run.data.blind_on_deck = Boss(TheClub);
+    run.data.ante_scaling = 0.001;
+    run.game_state = GameState::BlindSelection;
+
+    let mut next_ante = run.data.clone();
+    next_ante.ante += 1;
+    let expected = next_ante.poll_next_voucher();
+    assert_ne!(run.data.shop.vouchers, [expected]);
+
+    let mut controller = BeatBoss::default();
+    assert_eq!(run.simulate(&mut controller), SimulationResult::Aborted);
+    assert_eq!(controller.0, [expected]);
+}
+
 #[test]
 fn shop_inventory() {
     fn is(shop_item: &ShopItem, j_type: JokerType) -> bool {
@@ -45,7 +89,8 @@ fn shop_inventory() {
     assert!(is(&run.data.shop.inventory[0], JokerType::Bull));
     assert!(is(&run.data.shop.inventory[1], JokerType::FacelessJoker));
 
-    run.reroll();
+    run.data.money = 5.;
+    run.reroll().unwrap();
     assert!(is(&run.data.shop.inventory[0], JokerType::Misprint));
     assert_eq!(&run.data.shop.inventory[1], &ShopItem::Consumable(TarotCard(Tarot::TheSun)));
 
@@ -66,19 +111,206 @@ fn shop_packs() {
 
     assert_eq!(run.data.shop.packs, [Some(BuffoonNormal), Some(CelestialJumbo)]);
 
-    match run.open_booster_pack(BuffoonNormal) {
-        BoosterPackData::BuffoonNormal([a, b]) => {
-            assert!(is(&a, JokerType::ShootTheMoon));
-            assert!(is(&b, JokerType::ZanyJoker));
+    match run.open_booster_pack(BuffoonNormal).data {
+        BoosterPackData::Buffoon(jokers) => {
+            let [a, b] = &jokers[..] else { unreachable!() };
+            assert!(is(a, JokerType::ShootTheMoon));
+            assert!(is(b, JokerType::ZanyJoker));
         }
         _ => unreachable!(),
     }
 
     let e = [ThreeOfAKind.into(), Pair.into(), FourOfAKind.into(), TwoPair.into(), HighCard.into()];
-    match run.open_booster_pack(CelestialJumbo) {
-        BoosterPackData::CelestialJumbo(list) => {
+    match run.open_booster_pack(CelestialJumbo).data {
+        BoosterPackData::Celestial(list) => {
             assert_eq!(list, e);
         }
         _ => unreachable!(),
     }
 }
+
+#[test]
+fn voucher_purchases() {
+    let mut run = RunCreator::builder().seed("AAAAAAAA".to_string()).build().create();
+    run.data.money = 20.;
+    run.enter_shop(true);
+
+    let voucher = run.data.shop.vouchers[0];
+    assert_eq!(run.buy_voucher(0), Ok(voucher));
+    assert_eq!(run.data.money, 10.);
+    assert!(run.data.shop.vouchers.is_empty());
+    assert!(run.data.available_vouchers()[voucher.upgrade().unwrap() as usize]);
+
+    run.data.tags.push(VoucherTag);
+    run.enter_shop(false);
+    assert_eq!(run.data.shop.vouchers.len(), 1);
+    assert!(run.data.tags.is_empty());
+
+    run.data.apply_voucher_effects(Voucher::ClearanceSale);
+    run.data.money = 6.;
+    assert_eq!(run.buy_voucher(0), Err(ActionError::NotEnoughMoney));
+    run.data.money = 7.;
+    assert!(run.buy_voucher(0).is_ok());
+    assert_eq!(run.data.money, 0.);
+}
+
+#[test]
+fn blank_unlocks_antimatter() {
+    let mut run = RunCreator::builder().seed("AAAAAAAA".to_string()).build().create();
+    for voucher in Voucher::iter().filter(|v| ![Voucher::Blank, Voucher::Antimatter].contains(v)) {
+        run.data.apply_voucher_effects(voucher);
+    }
+
+    run.data.money = 10.;
+    run.enter_shop(true);
+    assert_eq!(run.data.shop.vouchers, [Voucher::Blank]);
+    assert_eq!(run.buy_voucher(0), Ok(Voucher::Blank));
+
+    assert_eq!(run.data.poll_next_voucher(), Voucher::Antimatter);
+}
+
+#[test]
+fn shop_playing_cards() {
+    let mut run = RunCreator::builder().seed("AAAAAAAA".to_string()).build().create();
+    run.data.apply_voucher_effects(Voucher::MagicTrick);
+    run.data.shop.weights = [0., 0., 0., 4., 0.];
+
+    let poll_cards = |run: &mut Run| {
+        (0..100)
+            .map(|_| match run.poll_next_shop_item() {
+                ShopItem::PlayingCard(card) => card,
+                _ => unreachable!(),
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let cards = poll_cards(&mut run);
+    assert!(cards.iter().all(|card| card.enhancement == Enhancement::None
+        && card.edition == Edition::Base
+        && card.seal == Seal::None));
+
+    run.data.apply_voucher_effects(Voucher::Illusion);
+    let cards = poll_cards(&mut run);
+    let enhanced = cards.iter().filter(|card| card.enhancement != Enhancement::None).count();
+    let editioned = cards.iter().filter(|card| card.edition != Edition::Base).count();
+
+    assert!((25..=55).contains(&enhanced));
+    assert!((5..=40).contains(&editioned));
+    assert!(cards.iter().all(|card| card.seal == Seal::None));
+}
+
+#[test]
+fn standard_pack_modifiers() {
+    let mut run = RunCreator::builder().seed("AAAAAAAA".to_string()).build().create();
+
+    let cards = (0..20)
+        .flat_map(|_| match run.open_booster_pack(StandardMega).data {
+            BoosterPackData::Standard(cards) => cards,
+            _ => unreachable!(),
+        })
+        .collect::<Vec<_>>();
+
+    let enhanced = cards.iter().filter(|card| card.enhancement != Enhancement::None).count();
+    let editioned = cards.iter().filter(|card| card.edition != Edition::Base).count();
+    let sealed = cards.iter().filter(|card| card.seal != Seal::None).count();
+
+    assert_eq!(cards.len(), 100);
+    assert!((25..=55).contains(&enhanced));
+    assert!((1..=20).contains(&editioned));
+    assert!((8..=35).contains(&sealed));
+}
+
+#[test]
+fn joker_editions() {
+    let editioned_jokers = |voucher: Option<Voucher>| {
+        let mut run = RunCreator::builder().seed("AAAAAAAA".to_string()).build().create();
+        if let Some(voucher) = voucher {
+            run.data.apply_voucher_effects(voucher);
+        }
+        run.data.shop.weights = [20., 0., 0., 0., 0.];
+
+        (0..200)
+            .filter(|_| match run.poll_next_shop_item() {
+                ShopItem::Joker(joker) => joker.edition != JokerEdition::Base,
+                _ => unreachable!(),
+            })
+            .count()
+    };
+
+    let base = editioned_jokers(None);
+    let glow_up = editioned_jokers(Some(Voucher::GlowUp));
+
+    assert!((1..=20).contains(&base));
+    assert!(glow_up > base);
+}
+
+#[test]
+fn pack_metadata() {
+    let mut run = RunCreator::builder().seed("AAAAAAAA".to_string()).build().create();
+
+    for pack_type in BoosterPackType::iter() {
+        let pack = run.open_booster_pack(pack_type);
+        assert_eq!(pack.pack_type, pack_type);
+        assert_eq!(pack.data.kind(), pack_type.kind());
+        assert_eq!(pack.data.card_count(), pack_type.card_count());
+        assert_eq!(pack.picks_left, pack_type.picks());
+    }
+
+    let mut pack = run.open_booster_pack(ArcanaMega);
+    assert_eq!(pack.pick(5), Err(PickError::OutOfRange(5)));
+    let BoosterPackData::Arcana(cards) = pack.data.clone() else { unreachable!() };
+    assert_eq!(pack.pick(4), Ok(BoosterPackItem::Arcana(cards[4])));
+    assert_eq!(pack.pick(0), Ok(BoosterPackItem::Arcana(cards[0])));
+    assert_eq!(pack.pick(0), Err(PickError::NoPicksLeft));
+    assert_eq!(pack.data, BoosterPackData::Arcana(cards[1..4].to_vec()));
+
+    assert_eq!((ArcanaMega.card_count(), ArcanaMega.picks()), (5, 2));
+    assert_eq!((BuffoonJumbo.card_count(), BuffoonJumbo.picks()), (4, 1));
+    assert_eq!(CelestialJumbo.kind(), BoosterPackKind::Celestial);
+    assert_eq!(StandardMega.base_cost(), 8.);
+}
+
+#[test]
+fn pack_purchases() {
+    let mut run = RunCreator::builder().seed("AAAAAAAA".to_string()).build().create();
+    run.enter_shop(true);
+    run.data.money = 10.;
+    run.data.joker_slots = 0;
+
+    // The first shop always has a Buffoon Pack
+    let mut pack = run.buy_booster_pack(0).unwrap();
+    assert_eq!(pack.pack_type, BuffoonNormal);
+    assert_eq!(run.data.money, 6.);
+    assert_eq!(run.data.shop.packs.iter().flatten().count(), 1);
+    assert_eq!(run.buy_booster_pack(1).unwrap_err(), ActionError::OutOfRange(1));
+
+    let BoosterPackData::Buffoon(jokers) = pack.data.clone() else { unreachable!() };
+    let no_room = jokers.iter().position(|joker| joker.edition != JokerEdition::Negative).unwrap();
+    assert_eq!(run.pick_from_booster_pack(&mut pack, no_room), Err(ActionError::NoRoom));
+
+    run.data.joker_slots = 5;
+    let picked = run.pick_from_booster_pack(&mut pack, 1).unwrap();
+    assert_eq!(picked, BoosterPackItem::Buffoon(jokers[1].clone()));
+    assert_eq!(run.jokers, [jokers[1].clone()]);
+    assert_eq!(run.pick_from_booster_pack(&mut pack, 0), Err(ActionError::NoPicksLeft));
+
+    let mut pack = run.open_booster_pack(CelestialJumbo);
+    let BoosterPackData::Celestial(cards) = pack.data.clone() else { unreachable!() };
+    let levels = run.data.hand_levels;
+    run.pick_from_booster_pack(&mut pack, 0).unwrap();
+    let leveled = HandType::iter()
+        .filter(|&hand| run.data.hand_levels[hand as usize] > levels[hand as usize]);
+    match cards[0] {
+        CelestialCard::Planet(hand_type) => assert_eq!(leveled.collect::<Vec<_>>(), [hand_type]),
+        CelestialCard::BlackHole => assert_eq!(leveled.count(), HandType::COUNT),
+    }
+
+    let mut pack = run.open_booster_pack(StandardMega);
+    let cards = run.data.cards.len();
+    run.pick_from_booster_pack(&mut pack, 0).unwrap();
+    assert_eq!(run.data.cards.len(), cards + 1);
+
+    let mut pack = run.open_booster_pack(ArcanaMega);
+    run.pick_from_booster_pack(&mut pack, 0).unwrap();
+    assert_eq!(run.data.consumables.len(), 1);
+}
@@ -0,0 +1,181 @@
+use balatro_logic::{
+    boosters::BoosterPackType::{BuffoonMega, CelestialNormal},
+    builders::{
+        joker::{
+            JokerCreator, JokerRarity,
+            JokerRarity::{Common, Rare, Uncommon},
+            JokerRarityMode,
+        },
+        run::RunCreator,
+    },
+    consumable::{
+        Consumable::{PlanetCard, TarotCard},
+        Tarot,
+    },
+    controller::ActionError,
+    hands::HandType::Pair,
+    joker::{
+        Joker,
+        JokerEdition::{Negative, Polychrome},
+        JokerType,
+        JokerType::{Astronomer, Blueprint, ChaosTheClown, CreditCard, Egg, GiftCard},
+    },
+    run::Run,
+    shop::ShopItem,
+    tags::Tag::{CouponTag, D6Tag},
+    vouchers::Voucher::{ClearanceSale, Liquidation, RerollSurplus},
+};
+
+fn new_run() -> Run {
+    RunCreator::builder().seed("AAAAAAAA".to_string()).build().create()
+}
+
+fn create_joker(run: &mut Run, rarity: JokerRarity, joker_type: JokerType) -> Joker {
+    JokerCreator::builder()
+        .origin_key("sho")
+        .joker_rarity(JokerRarityMode::Single(rarity))
+        .filter(&|candidate| *candidate == joker_type)
+        .build()
+        .create(run)
+}
+
+fn give_joker(run: &mut Run, rarity: JokerRarity, joker_type: JokerType) {
+    let joker = create_joker(run, rarity, joker_type);
+    run.jokers.push(joker);
+}
+
+#[test]
+fn base_costs_and_discounts() {
+    let mut run = new_run();
+    let mut blueprint = ShopItem::Joker(create_joker(&mut run, Rare, Blueprint));
+
+    assert_eq!(run.shop_item_cost(&blueprint), 10.);
+    assert_eq!(run.shop_item_cost(&ShopItem::Consumable(TarotCard(Tarot::TheFool))), 3.);
+    assert_eq!(run.booster_pack_cost(BuffoonMega), 8.);
+
+    if let ShopItem::Joker(joker) = &mut blueprint {
+        joker.edition = Polychrome;
+    }
+    assert_eq!(run.shop_item_cost(&blueprint), 15.);
+
+    run.data.apply_voucher_effects(ClearanceSale);
+    assert_eq!(run.shop_item_cost(&blueprint), 11.);
+
+    run.data.apply_voucher_effects(Liquidation);
+    assert_eq!(run.shop_item_cost(&blueprint), 7.);
+    assert_eq!(run.booster_pack_cost(BuffoonMega), 4.);
+}
+
+#[test]
+fn coupon_tag_and_astronomer() {
+    let mut run = new_run();
+    run.data.tags.push(CouponTag);
+    run.enter_shop(true);
+
+    for item in &run.data.shop.inventory {
+        assert_eq!(run.shop_item_cost(item), 0.);
+    }
+    assert_eq!(run.booster_pack_cost(BuffoonMega), 0.);
+
+    run.data.money = 5.;
+    run.reroll().unwrap();
+    assert!(run.data.shop.inventory.iter().all(|item| run.shop_item_cost(item) > 0.));
+    assert_eq!(run.booster_pack_cost(BuffoonMega), 0.);
+
+    run.enter_shop(false);
+    give_joker(&mut run, Uncommon, Astronomer);
+    assert_eq!(run.shop_item_cost(&ShopItem::Consumable(PlanetCard(Pair))), 0.);
+    assert_eq!(run.booster_pack_cost(CelestialNormal), 0.);
+    assert_eq!(run.booster_pack_cost(BuffoonMega), 8.);
+}
+
+#[test]
+fn sell_values() {
+    let mut run = new_run();
+    give_joker(&mut run, Common, Egg);
+    give_joker(&mut run, Uncommon, GiftCard);
+
+    let sell_values =
+        |run: &Run| run.jokers.iter().map(|joker| joker.sell_value).collect::<Vec<_>>();
+    let before = sell_values(&run);
+
+    run.round_ended();
+    assert_eq!(sell_values(&run), [before[0] + 4, before[1] + 1]);
+
+    let money = run.data.money;
+    let egg = run.sell_joker(0).unwrap();
+    assert_eq!(run.data.money, money + egg.sell_value as f64);
+}
+
+#[test]
+fn gift_card_consumables() {
+    let mut run = new_run();
+    give_joker(&mut run, Uncommon, GiftCard);
+    run.data.consumables.push(TarotCard(Tarot::TheFool));
+    let base = run.data.consumable_sell_value(0);
+
+    run.round_ended();
+    run.round_ended();
+    run.data.consumables.push(TarotCard(Tarot::TheFool));
+    assert_eq!(run.data.consumable_sell_value(0), base + 2.);
+    assert_eq!(run.data.consumable_sell_value(1), base);
+
+    let money = run.data.money;
+    run.sell_consumable(0).unwrap();
+    assert_eq!(run.data.money, money + base + 2.);
+    assert_eq!(run.data.consumable_sell_value(0), base);
+}
+
+#[test]
+fn buying_and_credit_card() {
+    let mut run = new_run();
+    run.data.money = 0.;
+    run.data.shop.inventory = vec![ShopItem::Consumable(PlanetCard(Pair))];
+    assert_eq!(run.buy_shop_item(0), Err(ActionError::NotEnoughMoney));
+
+    give_joker(&mut run, Common, CreditCard);
+    assert_eq!(run.buy_shop_item(0), Ok(ShopItem::Consumable(PlanetCard(Pair))));
+    assert_eq!(run.data.money, -3.);
+    assert_eq!(run.data.consumables.last(), Some(&PlanetCard(Pair)));
+
+    run.data.joker_slots = 1;
+    let mut joker = run.jokers[0].clone();
+    run.data.shop.inventory = vec![ShopItem::Joker(joker.clone())];
+    assert_eq!(run.buy_shop_item(0), Err(ActionError::NoRoom));
+
+    joker.edition = Negative;
+    run.data.shop.inventory = vec![ShopItem::Joker(joker)];
+    assert!(run.buy_shop_item(0).is_ok());
+}
+
+#[test]
+fn reroll_accounting() {
+    let mut run = new_run();
+    run.data.money = 11.;
+    run.enter_shop(true);
+
+    assert_eq!(run.data.shop.reroll_price, 5.);
+    run.reroll().unwrap();
+    assert_eq!(run.data.shop.reroll_price, 6.);
+    run.reroll().unwrap();
+    assert_eq!(run.data.money, 0.);
+    assert_eq!(run.reroll(), Err(ActionError::NotEnoughMoney));
+
+    run.data.apply_voucher_effects(RerollSurplus);
+    assert_eq!(run.data.shop.reroll_price, 5.);
+    run.enter_shop(false);
+    assert_eq!(run.data.shop.reroll_price, 3.);
+
+    run.data.tags.push(D6Tag);
+    run.enter_shop(false);
+    assert_eq!(run.data.shop.reroll_price, 0.);
+    run.reroll().unwrap();
+    assert_eq!(run.data.shop.reroll_price, 1.);
+
+    give_joker(&mut run, Common, ChaosTheClown);
+    run.enter_shop(false);
+    assert_eq!(run.data.shop.reroll_price, 0.);
+    run.reroll().unwrap();
+    assert_eq!(run.data.shop.reroll_price, 3.);
+    assert_eq!(run.data.money, 0.);
+}
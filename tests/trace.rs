@@ -0,0 +1,118 @@
+use balatro_logic::{
+    blind::{Blind, BlindType},
+    builders::run::RunCreator,
+    chance::Seeded,
+    consumable::Consumable::PlanetCard,
+    controller::{BlindAction, Controller, SimulationResult},
+    event::DispatcherOrder,
+    event_list::HandPlayedEventData,
+    hands::HandType::Pair,
+    joker::{Joker, JokerEdition, JokerInternalState, JokerType, Stickers},
+    observation::{BlindView, Observation},
+    scoring::score_hand_observed,
+    trace::{Source, TraceEvent},
+    vouchers::Voucher::Observatory,
+};
+
+#[test]
+fn contributions() {
+    let mut run = RunCreator::builder().seed("AAAAAAAA".to_string()).build().create();
+    run.jokers.push(Joker {
+        data: JokerInternalState::None,
+        joker_type: JokerType::JollyJoker,
+        edition: JokerEdition::Foil,
+        stickers: Stickers::default(),
+        sell_value: 0,
+        debuffed: false,
+        dispatcher_order: DispatcherOrder::default(),
+    });
+    run.data.apply_voucher_effects(Observatory);
+    run.data.consumables.push(PlanetCard(Pair));
+
+    let mut blind = Blind { hands: 1, held: vec![0, 13], ..Default::default() };
+    blind.select(0).unwrap();
+    blind.select(1).unwrap();
+    let hand = blind.prepare_play(&run.data).unwrap();
+
+    let mut events = Vec::new();
+    score_hand_observed(
+        &mut run.jokers,
+        &mut run.data,
+        &mut blind,
+        &mut HandPlayedEventData { hand, allowed: true },
+        &mut Seeded,
+        &mut events,
+    );
+
+    let contribution = |source, chips, mult| TraceEvent::Contribution { source, chips, mult };
+    assert_eq!(
+        events,
+        [
+            contribution(Source::Card(0), 2., 0.),
+            contribution(Source::Card(13), 2., 0.),
+            contribution(Source::JokerEdition(0), 50., 0.),
+            contribution(Source::Joker(0), 0., 8.),
+            contribution(Source::Consumable(0), 0., 5.),
+            TraceEvent::HandScored { chips: 64., mult: 15., score: 64. * 15. },
+        ]
+    );
+}
+
+#[test]
+fn simulation_trace() {
+    struct PlayFirst;
+
+    impl Controller for PlayFirst {
+        fn blind(&mut self, _: &Observation, _: &BlindView) -> Vec<BlindAction> {
+            vec![BlindAction::SelectCard(0), BlindAction::Play]
+        }
+    }
+
+    let mut events = Vec::new();
+    let run = RunCreator::builder().seed("AAAAAAAA".to_string()).build().create();
+    let result = run.simulate_observed(PlayFirst, &mut events);
+    assert!(matches!(result, SimulationResult::Lost { .. }));
+
+    assert!(matches!(
+        events[..2],
+        [
+            TraceEvent::BlindEntered { blind_type: BlindType::Small, requirement: 300. },
+            TraceEvent::CardsDrawn(ref cards)
+        ] if cards.len() == 8
+    ));
+
+    let played = events.iter().filter(|event| matches!(event, TraceEvent::HandPlayed { .. }));
+    let scored = events.iter().filter(|event| matches!(event, TraceEvent::HandScored { .. }));
+    let drawn = events
+        .iter()
+        .filter(|event| matches!(event, TraceEvent::CardsDrawn(cards) if cards.len() == 1));
+    assert_eq!(played.count(), 4);
+    assert_eq!(scored.count(), 4);
+    assert_eq!(drawn.count(), 4);
+}
+
+#[test]
+fn kickers_are_not_scoring() {
+    struct PlayFive;
+
+    impl Controller for PlayFive {
+        fn blind(&mut self, _: &Observation, _: &BlindView) -> Vec<BlindAction> {
+            (0..5).map(BlindAction::SelectCard).chain([BlindAction::Play]).collect()
+        }
+    }
+
+    let mut events = Vec::new();
+    let run = RunCreator::builder().seed("AAAAAAAA".to_string()).build().create();
+    run.simulate_observed(PlayFive, &mut events);
+
+    let played = events
+        .iter()
+        .filter_map(|event| match event {
+            TraceEvent::HandPlayed { cards, scoring, .. } => Some((cards, scoring)),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    assert!(!played.is_empty());
+    assert!(played.iter().all(|(cards, scoring)| scoring.iter().all(|idx| cards.contains(idx))));
+    assert!(played.iter().any(|(cards, scoring)| scoring.len() < cards.len()));
+}
@@ -0,0 +1,120 @@
+use balatro_logic::{
+    blind::Blind,
+    boosters::{
+        ArcanaCard, BoosterPackData,
+        BoosterPackType::{ArcanaMega, CelestialNormal},
+        CelestialCard,
+    },
+    builders::run::RunCreator,
+    card::{Edition, Enhancement},
+    consumable::Consumable::PlanetCard,
+    hands::HandType::{Flush, Pair},
+    run::Run,
+    shop::{ShopItem, ShopItemType},
+    vouchers::{Voucher, Voucher::*},
+};
+use strum::IntoEnumIterator;
+
+fn new_run() -> Run {
+    RunCreator::builder().seed("AAAAAAAA".to_string()).build().create()
+}
+
+#[test]
+fn every_voucher_has_an_effect() {
+    let fresh = new_run().data;
+
+    for voucher in Voucher::iter() {
+        let mut run = new_run();
+        run.data.apply_voucher_effects(voucher);
+        let data = &mut run.data;
+
+        match voucher {
+            Overstock | OverstockPlus => assert_eq!(data.shop.size, fresh.shop.size + 1),
+            ClearanceSale | Liquidation => assert_eq!(data.shop.price_multiplier, 0.75),
+            Hone => assert_eq!(data.shop.edition_rate, 2.),
+            GlowUp => assert_eq!(data.shop.edition_rate, 4.),
+            RerollSurplus | RerollGlut => {
+                assert_eq!(data.shop.base_reroll_price, fresh.shop.base_reroll_price - 2.);
+                assert_eq!(data.shop.reroll_price, fresh.shop.reroll_price - 2.);
+            }
+            CrystalBall => assert_eq!(data.consumable_slots, fresh.consumable_slots + 1),
+            OmenGlobe => {
+                let spectral_found =
+                    (0..20).any(|_| match run.open_booster_pack(ArcanaMega).data {
+                        BoosterPackData::Arcana(cards) => {
+                            cards.iter().any(|card| matches!(card, ArcanaCard::Spectral(_)))
+                        }
+                        _ => unreachable!(),
+                    });
+
+                assert!(spectral_found);
+            }
+            Telescope => {
+                data.times_played[Flush as usize] = 3;
+
+                match run.open_booster_pack(CelestialNormal).data {
+                    BoosterPackData::Celestial(cards) => {
+                        assert_eq!(cards[0], CelestialCard::Planet(Flush))
+                    }
+                    _ => unreachable!(),
+                }
+            }
+            Observatory => {
+                let mut blind = Blind { mult: 4., ..Default::default() };
+                PlanetCard(Pair).scored(data, &mut blind, Pair);
+                assert_eq!(blind.mult, 6.);
+            }
+            Grabber | NachoTong => assert_eq!(data.starting_hands, fresh.starting_hands + 1),
+            Wasteful | Recyclomancy => {
+                assert_eq!(data.starting_discards, fresh.starting_discards + 1)
+            }
+            TarotMerchant | TarotTycoon => assert!(
+                data.shop.weights[ShopItemType::Tarot as usize]
+                    > fresh.shop.weights[ShopItemType::Tarot as usize]
+            ),
+            PlanetMerchant | PlanetTycoon => assert!(
+                data.shop.weights[ShopItemType::Planet as usize]
+                    > fresh.shop.weights[ShopItemType::Planet as usize]
+            ),
+            SeedMoney | MoneyTree => {
+                data.money = 200.;
+                assert!(data.interest() > fresh.interest_cap / 5.);
+            }
+            Blank => assert!(Antimatter.requirements_fulfilled(&data.vouchers)),
+            Antimatter => assert_eq!(data.joker_slots, fresh.joker_slots + 1),
+            MagicTrick => assert_eq!(data.shop.weights[ShopItemType::PlayingCard as usize], 4.),
+            Illusion => {
+                data.shop.weights = [0., 0., 0., 4., 0.];
+
+                let modified_card_found = (0..30).any(|_| match run.poll_next_shop_item() {
+                    ShopItem::PlayingCard(card) => {
+                        card.enhancement != Enhancement::None || card.edition != Edition::Base
+                    }
+                    _ => unreachable!(),
+                });
+
+                assert!(modified_card_found);
+            }
+            Hieroglyph => {
+                assert_eq!(data.ante, fresh.ante - 1);
+                assert_eq!(data.starting_hands, fresh.starting_hands - 1);
+            }
+            Petroglyph => {
+                assert_eq!(data.ante, fresh.ante - 1);
+                assert_eq!(data.starting_discards, fresh.starting_discards - 1);
+            }
+            DirectorsCut => {
+                data.money = 20.;
+                assert!(data.reroll_boss().is_some());
+                assert!(data.reroll_boss().is_none());
+            }
+            Retcon => {
+                data.money = 20.;
+                assert!(data.reroll_boss().is_some());
+                assert!(data.reroll_boss().is_some());
+                assert!(data.reroll_boss().is_none());
+            }
+            PaintBrush | Palette => assert_eq!(data.hand_size, fresh.hand_size + 1),
+        }
+    }
+}